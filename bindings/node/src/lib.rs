@@ -0,0 +1,108 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Node.js bindings for [`tinyid`], so JavaScript and TypeScript services validate IDs with
+//! exactly the same rules as the Rust backend.
+
+#![deny(missing_docs)]
+
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+/// Convert a parse failure into a JavaScript `Error`.
+fn invalid(input: &str, err: &tinyid::TinyIdError) -> Error {
+    Error::new(
+        Status::InvalidArg,
+        format!("invalid TinyId {input:?}: {err}"),
+    )
+}
+
+/// Generate a new random ID, returned as a string.
+#[napi]
+#[must_use]
+pub fn generate() -> String {
+    tinyid::TinyId::random().to_string()
+}
+
+/// Parse `input` as an ID, returning it unchanged if it is valid and throwing otherwise.
+///
+/// ## Errors
+/// Throws if `input` is not a valid ID.
+#[napi]
+#[allow(clippy::needless_pass_by_value)]
+pub fn parse(input: String) -> Result<String> {
+    input
+        .parse::<tinyid::TinyId>()
+        .map(|id| id.to_string())
+        .map_err(|err| invalid(&input, &err))
+}
+
+/// Check whether `input` is a valid ID.
+#[napi]
+#[must_use]
+#[allow(clippy::needless_pass_by_value)]
+pub fn validate(input: String) -> bool {
+    input.parse::<tinyid::TinyId>().is_ok()
+}
+
+/// A parsed ID, exposed to JavaScript as the `TinyId` class.
+#[napi(js_name = "TinyId")]
+pub struct JsTinyId {
+    inner: tinyid::TinyId,
+}
+
+#[napi]
+impl JsTinyId {
+    /// Generate a new random ID.
+    #[napi(factory)]
+    #[must_use]
+    pub fn random() -> Self {
+        Self {
+            inner: tinyid::TinyId::random(),
+        }
+    }
+
+    /// Parse `input` as an ID.
+    ///
+    /// ## Errors
+    /// Throws if `input` is not a valid ID.
+    #[napi(factory)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn parse(input: String) -> Result<Self> {
+        input
+            .parse()
+            .map(|inner| Self { inner })
+            .map_err(|err| invalid(&input, &err))
+    }
+
+    /// Whether this ID is valid.
+    #[napi]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    /// Whether this ID is null.
+    #[napi]
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        self.inner.is_null()
+    }
+
+    /// Whether this ID is the same as `other`.
+    #[napi]
+    #[must_use]
+    pub fn equals(&self, other: &JsTinyId) -> bool {
+        self.inner == other.inner
+    }
+
+    /// The string form of this ID.
+    #[napi(js_name = "toString")]
+    #[must_use]
+    pub fn to_js_string(&self) -> String {
+        self.inner.to_string()
+    }
+}
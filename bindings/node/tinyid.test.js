@@ -0,0 +1,35 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+const test = require('node:test');
+const assert = require('node:assert');
+const tinyid = require('./index.js');
+
+test('generate produces valid ids', () => {
+  for (let i = 0; i < 100; i++) {
+    const id = tinyid.generate();
+    assert.strictEqual(id.length, 8);
+    assert.ok(tinyid.validate(id));
+    assert.strictEqual(tinyid.parse(id), id);
+  }
+});
+
+test('invalid ids are rejected', () => {
+  assert.ok(!tinyid.validate('short'));
+  assert.ok(!tinyid.validate('abc!efgh'));
+  assert.throws(() => tinyid.parse('abc!efgh'));
+  assert.throws(() => tinyid.TinyId.parse('toolongforanid'));
+});
+
+test('TinyId class', () => {
+  const id = tinyid.TinyId.parse('abcdefgh');
+  assert.strictEqual(id.toString(), 'abcdefgh');
+  assert.strictEqual(`${id}`, 'abcdefgh');
+  assert.ok(id.isValid());
+  assert.ok(!id.isNull());
+  assert.ok(id.equals(tinyid.TinyId.parse('abcdefgh')));
+  assert.ok(!id.equals(tinyid.TinyId.random()));
+});
@@ -6,6 +6,11 @@ fn main() {
     // Create a random ID
     let rand_id = TinyId::random();
 
+    // Create a random ID that is not already in a set, and add it to the set
+    let mut used = std::collections::HashSet::new();
+    let unique_id = TinyId::random_unique(&mut used);
+    assert!(used.contains(&unique_id));
+
     // Parse a string into a Result<TinyId, TinyIdError> for possibly unsafe / invalid ID strings
     let maybe = TinyId::from_str("AAAABBBB");
     assert!(maybe.is_ok());
@@ -10,13 +10,6 @@ const ITERS: usize = 100;
 
 fn main() {
     println!("Generating {ITERS} TinyIds...");
-    for x in (0..(ITERS)).step_by(2) {
-        let id = TinyId::random();
-        let mut n = x + 1;
-        print!("#{n:03}: {id}");
-        print!(" | ");
-        let id = TinyId::random();
-        n += 1;
-        println!("#{n:03}: {id}");
-    }
+    let ids = (0..ITERS).map(|_| TinyId::random());
+    print!("{}", tinyid::fmt::table(ids, 2));
 }
@@ -0,0 +1,408 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+
+use crate::{TinyId, TinyIdError};
+
+/// An ordered subset of [`TinyId::LETTERS`], used to describe which characters an application's
+/// IDs are drawn from.
+///
+/// By default every letter is equally likely to be generated, but per-letter weights can be
+/// attached with [`Alphabet::with_weights`] to bias generation without changing which letters
+/// are considered valid.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    letters: Cow<'static, [u8]>,
+    weights: Option<Vec<u32>>,
+}
+
+impl Alphabet {
+    /// The full alphabet used by [`TinyId`], in the same order as [`TinyId::LETTERS`].
+    pub const STANDARD: Alphabet = Alphabet {
+        letters: Cow::Borrowed(&TinyId::LETTERS),
+        weights: None,
+    };
+
+    /// [`Alphabet::STANDARD`] without the easily confused characters `0`, `O`, `1`, `l` and `I`.
+    pub const UNAMBIGUOUS: Alphabet = Alphabet {
+        letters: Cow::Borrowed(b"abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789_-"),
+        weights: None,
+    };
+
+    /// Lowercase letters and digits only, for case-insensitive systems.
+    pub const LOWERCASE: Alphabet = Alphabet {
+        letters: Cow::Borrowed(b"abcdefghijklmnopqrstuvwxyz1234567890"),
+        weights: None,
+    };
+
+    /// Create a custom [`Alphabet`] from the given letters, in order.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `letters` is empty.
+    /// - [`TinyIdError::InvalidCharacters`] if `letters` contains a character that is not valid
+    ///   in a [`TinyId`], or contains the same character twice.
+    pub fn new(letters: &str) -> Result<Self, TinyIdError> {
+        let letters = letters.as_bytes();
+        if letters.is_empty() {
+            return Err(TinyIdError::InvalidLength);
+        }
+        for (i, &letter) in letters.iter().enumerate() {
+            if !TinyId::is_valid_byte(letter) || letters[..i].contains(&letter) {
+                return Err(TinyIdError::InvalidCharacters);
+            }
+        }
+        Ok(Self {
+            letters: Cow::Owned(letters.to_vec()),
+            weights: None,
+        })
+    }
+
+    /// Attach a weight to each letter of this alphabet, in the same order as
+    /// [`Alphabet::letters`], so that [`Alphabet::random_id`] picks each letter with probability
+    /// proportional to its weight. A letter with a weight of zero is never generated, but is still
+    /// accepted by [`Alphabet::is_valid`] and [`TinyId::remap`].
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there is not exactly one weight per letter, or every
+    ///   weight is zero.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::Alphabet;
+    ///
+    /// // Mostly lowercase letters, but any letter is still valid.
+    /// let weights: Vec<u32> = Alphabet::STANDARD
+    ///     .letters()
+    ///     .iter()
+    ///     .map(|b| if b.is_ascii_lowercase() { 100 } else { 1 })
+    ///     .collect();
+    /// let alphabet = Alphabet::STANDARD.with_weights(&weights).unwrap();
+    /// assert_eq!(alphabet.weight(b'a'), Some(100));
+    /// assert!(alphabet.is_valid(alphabet.random_id()));
+    /// ```
+    pub fn with_weights(mut self, weights: &[u32]) -> Result<Self, TinyIdError> {
+        if weights.len() != self.letters.len() || weights.iter().all(|&w| w == 0) {
+            return Err(TinyIdError::InvalidLength);
+        }
+        self.weights = Some(weights.to_vec());
+        Ok(self)
+    }
+
+    /// Get the generation weight of `letter`, or `None` if it is not part of this alphabet. Every
+    /// letter has a weight of 1 unless weights were attached with [`Alphabet::with_weights`].
+    #[must_use]
+    pub fn weight(&self, letter: u8) -> Option<u32> {
+        let position = self.position(letter)?;
+        Some(self.weights.as_ref().map_or(1, |weights| weights[position]))
+    }
+
+    /// Checks whether weights have been attached to this alphabet.
+    #[must_use]
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// The letters of this alphabet, in order.
+    #[must_use]
+    pub fn letters(&self) -> &[u8] {
+        &self.letters
+    }
+
+    /// The number of letters in this alphabet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Always `false`, as an [`Alphabet`] can not be empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// Checks whether `letter` is part of this alphabet.
+    #[must_use]
+    pub fn contains(&self, letter: u8) -> bool {
+        self.letters.contains(&letter)
+    }
+
+    /// Checks whether every character of `id` is part of this alphabet.
+    #[must_use]
+    pub fn is_valid(&self, id: TinyId) -> bool {
+        id.data.iter().all(|&b| self.contains(b))
+    }
+
+    /// Create a new random [`TinyId`] drawn only from this alphabet's letters. Since the
+    /// alphabet was validated when it was created, this never fails.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::Alphabet;
+    ///
+    /// let digits = Alphabet::new("0123456789").unwrap();
+    /// let id = digits.random_id();
+    /// assert!(id.is_valid());
+    /// assert!(id.to_string().bytes().all(|b| b.is_ascii_digit()));
+    /// ```
+    #[must_use]
+    pub fn random_id(&self) -> TinyId {
+        let mut data = TinyId::NULL_DATA;
+        for b in &mut data {
+            *b = self.random_letter();
+        }
+        TinyId { data }
+    }
+
+    /// Pick a random letter from this alphabet, honoring its weights if it has any.
+    fn random_letter(&self) -> u8 {
+        let Some(weights) = &self.weights else {
+            return self.letters[crate::rng::usize_below(self.letters.len())];
+        };
+        let total: u64 = weights.iter().map(|&w| u64::from(w)).sum();
+        let mut target = crate::rng::u64_below(total);
+        for (&letter, &weight) in self.letters.iter().zip(weights) {
+            match target.checked_sub(u64::from(weight)) {
+                Some(rest) => target = rest,
+                None => return letter,
+            }
+        }
+        unreachable!("target is always less than the total weight")
+    }
+
+    /// Get the position of `letter` within this alphabet.
+    fn position(&self, letter: u8) -> Option<usize> {
+        self.letters.iter().position(|&b| b == letter)
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl TinyId {
+    /// Create a new random [`TinyId`] drawn only from the letters in `charset`, e.g. digits only.
+    /// To generate many IDs, create the [`Alphabet`] once and use [`Alphabet::random_id`] so the
+    /// charset is only validated once.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `charset` is empty.
+    /// - [`TinyIdError::InvalidCharacters`] if `charset` contains a character that is not valid
+    ///   in a [`TinyId`], or contains the same character twice.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random_in("abc").unwrap();
+    /// assert!(id.to_string().bytes().all(|b| b"abc".contains(&b)));
+    /// assert!(TinyId::random_in("abc!").is_err());
+    /// ```
+    pub fn random_in(charset: &str) -> Result<TinyId, TinyIdError> {
+        Alphabet::new(charset).map(|alphabet| alphabet.random_id())
+    }
+
+    /// Convert this [`TinyId`] from one [`Alphabet`] to another, by replacing each character with
+    /// the character at the same position in `to` as it had in `from`. Converting back with the
+    /// alphabets swapped gives the original ID. The null ID is returned unchanged.
+    ///
+    /// The built-in alphabets all start with the lowercase letters, so IDs made only of the
+    /// letters they share a position for (e.g. all-lowercase IDs between [`Alphabet::STANDARD`]
+    /// and [`Alphabet::LOWERCASE`]) are unchanged.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if this ID contains a character not in `from`.
+    /// - [`TinyIdError::Conversion`] if this ID contains a character whose position in `from` is
+    ///   past the end of `to`, which can happen when moving to a smaller alphabet.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{Alphabet, TinyId};
+    ///
+    /// let id = TinyId::from_str_unchecked("abcxyzqr");
+    /// let lower = id.remap(&Alphabet::STANDARD, &Alphabet::LOWERCASE).unwrap();
+    /// assert_eq!(lower, id);
+    ///
+    /// let id = TinyId::from_str_unchecked("hello-AB");
+    /// let lower = id.remap(&Alphabet::STANDARD, &Alphabet::LOWERCASE);
+    /// assert!(lower.is_err());
+    /// ```
+    pub fn remap(&self, from: &Alphabet, to: &Alphabet) -> Result<TinyId, TinyIdError> {
+        if self.is_null() {
+            return Ok(*self);
+        }
+        let mut data = Self::NULL_DATA;
+        for (out, &letter) in data.iter_mut().zip(self.data.iter()) {
+            let position = from
+                .position(letter)
+                .ok_or(TinyIdError::InvalidCharacters)?;
+            *out = *to.letters.get(position).ok_or_else(|| {
+                TinyIdError::Conversion(format!(
+                    "'{}' has no equivalent in an alphabet of {} letters",
+                    letter as char,
+                    to.len()
+                ))
+            })?;
+        }
+        Ok(Self { data })
+    }
+
+    /// Lazily [`remap`](TinyId::remap) every ID in `ids`, for migrating stored IDs in bulk.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{Alphabet, TinyId};
+    ///
+    /// let ids = [TinyId::from_str_unchecked("abcdefgh"), TinyId::from_str_unchecked("ABCDEFGH")];
+    /// let migrated = TinyId::remap_all(ids, &Alphabet::STANDARD, &Alphabet::UNAMBIGUOUS)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(migrated[0].to_string(), "abcdefgh");
+    /// ```
+    pub fn remap_all<'a, I>(
+        ids: I,
+        from: &'a Alphabet,
+        to: &'a Alphabet,
+    ) -> impl Iterator<Item = Result<TinyId, TinyIdError>> + 'a
+    where
+        I: IntoIterator<Item = TinyId>,
+        I::IntoIter: 'a,
+    {
+        ids.into_iter().map(move |id| id.remap(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn builtin() {
+        for alphabet in [
+            Alphabet::STANDARD,
+            Alphabet::UNAMBIGUOUS,
+            Alphabet::LOWERCASE,
+        ] {
+            let new = Alphabet::new(std::str::from_utf8(alphabet.letters()).unwrap()).unwrap();
+            assert_eq!(new, alphabet);
+            assert!(!alphabet.is_empty());
+        }
+        assert_eq!(Alphabet::default().len(), TinyId::LETTER_COUNT);
+        assert_eq!(Alphabet::UNAMBIGUOUS.len(), TinyId::LETTER_COUNT - 5);
+        assert_eq!(Alphabet::LOWERCASE.len(), 36);
+        for ch in *b"01IOl" {
+            assert!(!Alphabet::UNAMBIGUOUS.contains(ch));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn custom() {
+        assert_eq!(Alphabet::new(""), Err(TinyIdError::InvalidLength));
+        assert_eq!(Alphabet::new("abca"), Err(TinyIdError::InvalidCharacters));
+        assert_eq!(Alphabet::new("ab!"), Err(TinyIdError::InvalidCharacters));
+        let digits = Alphabet::new("0123456789").unwrap();
+        assert!(digits.is_valid(TinyId::from_str_unchecked("01234567")));
+        assert!(!digits.is_valid(TinyId::from_str_unchecked("0123456a")));
+        assert!(!digits.is_valid(TinyId::null()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random_in() {
+        let digits = Alphabet::new("0123456789").unwrap();
+        let single = Alphabet::new("x").unwrap();
+        for _ in 0..1000 {
+            let id = digits.random_id();
+            assert!(id.is_valid());
+            assert!(digits.is_valid(id));
+            assert!(Alphabet::LOWERCASE.is_valid(Alphabet::LOWERCASE.random_id()));
+        }
+        assert_eq!(single.random_id().to_string(), "xxxxxxxx");
+        let id = TinyId::random_in("AB").unwrap();
+        assert!(id.to_string().bytes().all(|b| b == b'A' || b == b'B'));
+        assert_eq!(TinyId::random_in(""), Err(TinyIdError::InvalidLength));
+        assert_eq!(
+            TinyId::random_in("0 1"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn remap() {
+        let standard = Alphabet::STANDARD;
+        let unambiguous = Alphabet::UNAMBIGUOUS;
+        let lowercase = Alphabet::LOWERCASE;
+
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            if let Ok(remapped) = id.remap(&standard, &unambiguous) {
+                assert!(unambiguous.is_valid(remapped));
+                assert_eq!(remapped.remap(&unambiguous, &standard), Ok(id));
+            }
+        }
+
+        let id = TinyId::from_str_unchecked("a1b2c3d4");
+        let remapped = id.remap(&lowercase, &standard).unwrap();
+        assert_eq!(remapped.to_string(), "aAbBcCdD");
+        assert_eq!(remapped.remap(&standard, &lowercase), Ok(id));
+        assert_eq!(
+            id.remap(&unambiguous, &standard),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert!(matches!(
+            TinyId::from_str_unchecked("--------").remap(&standard, &lowercase),
+            Err(TinyIdError::Conversion(_))
+        ));
+        assert_eq!(
+            TinyId::null().remap(&standard, &lowercase),
+            Ok(TinyId::null())
+        );
+
+        let ids = [id, TinyId::from_str_unchecked("________")];
+        let results = TinyId::remap_all(ids, &lowercase, &standard).collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], Ok(remapped));
+        assert_eq!(results[1], Err(TinyIdError::InvalidCharacters));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn weighted() {
+        let alphabet = Alphabet::new("abc").unwrap();
+        assert!(!alphabet.is_weighted());
+        assert_eq!(alphabet.weight(b'a'), Some(1));
+        assert_eq!(alphabet.weight(b'z'), None);
+        assert_eq!(
+            alphabet.clone().with_weights(&[1, 2]),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            alphabet.clone().with_weights(&[0, 0, 0]),
+            Err(TinyIdError::InvalidLength)
+        );
+
+        let weighted = alphabet.with_weights(&[0, 1, 3]).unwrap();
+        assert!(weighted.is_weighted());
+        assert_eq!(weighted.weight(b'a'), Some(0));
+        assert_eq!(weighted.weight(b'c'), Some(3));
+        assert!(weighted.is_valid(TinyId::from_str_unchecked("aaaaaaaa")));
+        let mut counts = [0usize; 3];
+        for _ in 0..1000 {
+            let id = weighted.random_id();
+            assert!(weighted.is_valid(id));
+            for b in id.to_string().bytes() {
+                counts[usize::from(b - b'a')] += 1;
+            }
+        }
+        assert_eq!(counts[0], 0);
+        assert!(counts[2] > counts[1] * 2);
+    }
+}
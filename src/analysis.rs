@@ -0,0 +1,240 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Measurements of how [`TinyId`] generation behaves on the current machine: how many IDs can be
+//! generated before a collision, how quickly they can be generated, and whether every letter is
+//! generated equally often.
+//!
+//! **Beware**, with the default generator a single collision run usually takes tens of millions
+//! of IDs (and a `HashSet` large enough to hold them), so these can take quite a while.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::{IdProvider, RandomProvider, TinyId};
+
+/// Generate random IDs until one repeats, returning how many were generated (including the
+/// repeat).
+#[must_use]
+pub fn run_until_collision() -> usize {
+    run_until_collision_with(RandomProvider)
+}
+
+/// Take IDs from `provider` until one repeats, returning how many were taken (including the
+/// repeat). Never returns if `provider` never repeats itself.
+///
+/// ## Example
+/// ```
+/// use tinyid::{analysis, Pronounceable};
+///
+/// // Pronounceable IDs have much less entropy, so they collide quickly.
+/// let count = analysis::run_until_collision_with(Pronounceable);
+/// assert!(count >= 2);
+/// ```
+pub fn run_until_collision_with(mut provider: impl IdProvider) -> usize {
+    let mut ids = HashSet::new();
+    loop {
+        if !ids.insert(provider.next_id()) {
+            return ids.len() + 1;
+        }
+    }
+}
+
+/// Run [`run_until_collision`] `runs` times, returning the average number of IDs generated
+/// before a collision. Returns `0` if `runs` is `0`.
+#[must_use]
+pub fn average_collision_point(runs: usize) -> usize {
+    average_collision_point_with(runs, || RandomProvider)
+}
+
+/// Run [`run_until_collision_with`] `runs` times, each with a fresh provider from `make`,
+/// returning the average number of IDs taken before a collision. Returns `0` if `runs` is `0`.
+pub fn average_collision_point_with<P: IdProvider>(
+    runs: usize,
+    mut make: impl FnMut() -> P,
+) -> usize {
+    if runs == 0 {
+        return 0;
+    }
+    (0..runs)
+        .map(|_| run_until_collision_with(make()))
+        .sum::<usize>()
+        / runs
+}
+
+/// Run `f`, returning its result along with how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Measure how long it takes to generate `count` random IDs.
+#[must_use]
+pub fn time_generation(count: usize) -> Duration {
+    time_generation_with(RandomProvider, count)
+}
+
+/// Measure how long it takes to take `count` IDs from `provider`.
+pub fn time_generation_with(mut provider: impl IdProvider, count: usize) -> Duration {
+    time(|| {
+        for _ in 0..count {
+            std::hint::black_box::<TinyId>(provider.next_id());
+        }
+    })
+    .1
+}
+
+/// Measure how many random IDs can be generated per second, by generating `count` of them.
+#[allow(clippy::cast_precision_loss)]
+#[must_use]
+pub fn ids_per_second(count: usize) -> f64 {
+    count as f64 / time_generation(count).as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+/// Count how often each letter occurs in `ids`, indexed by the letter's position in
+/// [`TinyId::LETTERS`]. Invalid bytes (e.g. in the null ID) are not counted. Pass the result to
+/// [`uniformity_score`] to check a generator for bias.
+///
+/// ## Example
+/// ```
+/// use tinyid::{analysis, TinyId};
+///
+/// let freq = analysis::char_frequency([TinyId::from_str_unchecked("aaaabbbb")]);
+/// assert_eq!(freq[0], 4);
+/// assert_eq!(freq[1], 4);
+/// assert_eq!(freq.iter().sum::<u64>(), 8);
+/// ```
+pub fn char_frequency(ids: impl IntoIterator<Item = TinyId>) -> [u64; TinyId::LETTER_COUNT] {
+    let mut positions = [None; 256];
+    for (i, &letter) in TinyId::LETTERS.iter().enumerate() {
+        positions[usize::from(letter)] = Some(i);
+    }
+    let mut freq = [0; TinyId::LETTER_COUNT];
+    for id in ids {
+        for b in id.to_bytes() {
+            if let Some(i) = positions[usize::from(b)] {
+                freq[i] += 1;
+            }
+        }
+    }
+    freq
+}
+
+/// Score how uniformly letters are distributed in a [`char_frequency`] result, as Pearson's
+/// chi-squared statistic against every letter being equally likely. Lower is more uniform;
+/// returns `0.0` if no letters were counted.
+///
+/// For an unbiased generator the score averages 63 (the degrees of freedom), and a score above
+/// about 103 happens by chance less than 0.1% of the time, so a consistently higher score means
+/// the generator is biased. Count at least a few thousand IDs for the score to be meaningful.
+///
+/// ## Example
+/// ```
+/// use tinyid::{analysis, Pronounceable, TinyId, IdProvider};
+///
+/// let random = analysis::char_frequency((0..10_000).map(|_| TinyId::random()));
+/// assert!(analysis::uniformity_score(&random) < 150.0);
+///
+/// let mut gen = Pronounceable;
+/// let biased = analysis::char_frequency((0..10_000).map(|_| gen.next_id()));
+/// assert!(analysis::uniformity_score(&biased) > 1_000.0);
+/// ```
+#[allow(clippy::cast_precision_loss)]
+#[must_use]
+pub fn uniformity_score(freq: &[u64; TinyId::LETTER_COUNT]) -> f64 {
+    let total: u64 = freq.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let expected = total as f64 / TinyId::LETTER_COUNT as f64;
+    freq.iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+/// Format `n` with commas separating each group of three digits, e.g. `20,000,000`.
+///
+/// ## Example
+/// ```
+/// assert_eq!(tinyid::analysis::format_count(20_000_000), "20,000,000");
+/// ```
+#[must_use]
+pub fn format_count(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FixedProvider, Pronounceable};
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn collisions() {
+        let fixed = FixedProvider::new(TinyId::random());
+        assert_eq!(run_until_collision_with(fixed), 2);
+        assert_eq!(average_collision_point_with(3, || fixed), 2);
+        assert_eq!(average_collision_point_with(0, || fixed), 0);
+        let average = average_collision_point_with(5, || Pronounceable);
+        assert!(average > 100 && average < 1_000_000, "{average}");
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn timing() {
+        let (value, elapsed) = time(|| 42);
+        assert_eq!(value, 42);
+        assert!(elapsed < Duration::from_secs(1));
+        assert!(time_generation(1_000) < Duration::from_secs(1));
+        assert!(ids_per_second(10_000) > 0.0);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn frequency() {
+        let freq = char_frequency([
+            TinyId::from_str_unchecked("abcdefgh"),
+            TinyId::from_str_unchecked("-_-_0909"),
+            TinyId::null(),
+            TinyId::from_bytes_unchecked(*b"a\0\xffzzzzz"),
+        ]);
+        assert_eq!(freq.iter().sum::<u64>(), 22);
+        let count = |letter: u8| freq[TinyId::LETTERS.iter().position(|&l| l == letter).unwrap()];
+        assert_eq!(count(b'a'), 2);
+        assert_eq!(count(b'z'), 5);
+        assert_eq!(count(b'-'), 2);
+        assert_eq!(count(b'0'), 2);
+        assert_eq!(count(b'A'), 0);
+
+        assert!(uniformity_score(&[0; TinyId::LETTER_COUNT]).abs() < f64::EPSILON);
+        assert!(uniformity_score(&[5; TinyId::LETTER_COUNT]).abs() < f64::EPSILON);
+        let mut skewed = [0; TinyId::LETTER_COUNT];
+        skewed[0] = 64;
+        assert!((uniformity_score(&skewed) - 64.0 * 63.0).abs() < 1e-9);
+
+        let random = char_frequency((0..10_000).map(|_| TinyId::random()));
+        assert_eq!(random.iter().sum::<u64>(), 80_000);
+        assert!(uniformity_score(&random) < 150.0);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn format() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1_000), "1,000");
+        assert_eq!(format_count(123_456_789), "123,456,789");
+    }
+}
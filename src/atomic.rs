@@ -0,0 +1,160 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::TinyId;
+
+/// A [`TinyId`] which can be safely shared between threads, backed by an [`AtomicU64`].
+///
+/// The null ID (see [`TinyId::null`]) is used as the "empty" state, which is also what
+/// [`AtomicTinyId::default`] contains.
+///
+/// ## Example
+/// ```
+/// use std::sync::atomic::Ordering;
+/// use tinyid::{AtomicTinyId, TinyId};
+///
+/// let slot = AtomicTinyId::null();
+/// let id = TinyId::random();
+/// assert!(slot.compare_exchange(TinyId::null(), id, Ordering::AcqRel, Ordering::Acquire).is_ok());
+/// assert_eq!(slot.load(Ordering::Acquire), id);
+/// assert_eq!(slot.take(Ordering::AcqRel), id);
+/// assert!(slot.is_null(Ordering::Acquire));
+/// ```
+#[derive(Debug, Default)]
+pub struct AtomicTinyId {
+    inner: AtomicU64,
+}
+
+impl AtomicTinyId {
+    /// Create a new [`AtomicTinyId`] holding the given ID.
+    #[must_use]
+    pub fn new(id: TinyId) -> Self {
+        Self {
+            inner: AtomicU64::new(id.to_u64()),
+        }
+    }
+
+    /// Create a new, empty [`AtomicTinyId`] holding the null ID.
+    #[must_use]
+    pub fn null() -> Self {
+        Self::new(TinyId::null())
+    }
+
+    /// Loads the current ID. See [`AtomicU64::load`].
+    #[must_use]
+    pub fn load(&self, order: Ordering) -> TinyId {
+        TinyId::from_u64_unchecked(self.inner.load(order))
+    }
+
+    /// Stores a new ID. See [`AtomicU64::store`].
+    pub fn store(&self, id: TinyId, order: Ordering) {
+        self.inner.store(id.to_u64(), order);
+    }
+
+    /// Stores a new ID, returning the previous one. See [`AtomicU64::swap`].
+    pub fn swap(&self, id: TinyId, order: Ordering) -> TinyId {
+        TinyId::from_u64_unchecked(self.inner.swap(id.to_u64(), order))
+    }
+
+    /// Stores `new` if the current ID is `current`. See [`AtomicU64::compare_exchange`].
+    ///
+    /// ## Errors
+    /// Returns the actual current ID if it was not equal to `current`.
+    pub fn compare_exchange(
+        &self,
+        current: TinyId,
+        new: TinyId,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<TinyId, TinyId> {
+        self.inner
+            .compare_exchange(current.to_u64(), new.to_u64(), success, failure)
+            .map(TinyId::from_u64_unchecked)
+            .map_err(TinyId::from_u64_unchecked)
+    }
+
+    /// Takes the current ID, leaving the null ID in its place.
+    pub fn take(&self, order: Ordering) -> TinyId {
+        self.swap(TinyId::null(), order)
+    }
+
+    /// Checks whether the current ID is null.
+    #[must_use]
+    pub fn is_null(&self, order: Ordering) -> bool {
+        self.load(order).is_null()
+    }
+
+    /// Consumes the atomic, returning the contained ID.
+    #[must_use]
+    pub fn into_inner(self) -> TinyId {
+        TinyId::from_u64_unchecked(self.inner.into_inner())
+    }
+}
+
+impl From<TinyId> for AtomicTinyId {
+    fn from(id: TinyId) -> Self {
+        Self::new(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn basic_usage() {
+        let slot = AtomicTinyId::default();
+        assert!(slot.is_null(Ordering::SeqCst));
+
+        let a = TinyId::random();
+        let b = TinyId::random();
+        slot.store(a, Ordering::SeqCst);
+        assert_eq!(slot.load(Ordering::SeqCst), a);
+        assert_eq!(slot.swap(b, Ordering::SeqCst), a);
+        assert_eq!(
+            slot.compare_exchange(a, a, Ordering::SeqCst, Ordering::SeqCst),
+            Err(b)
+        );
+        assert_eq!(
+            slot.compare_exchange(b, a, Ordering::SeqCst, Ordering::SeqCst),
+            Ok(b)
+        );
+        assert_eq!(slot.take(Ordering::SeqCst), a);
+        assert!(slot.is_null(Ordering::SeqCst));
+        assert_eq!(AtomicTinyId::from(b).into_inner(), b);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn threads() {
+        let slot = AtomicTinyId::null();
+        let winners = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        slot.compare_exchange(
+                            TinyId::null(),
+                            TinyId::random(),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_ok()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|h| h.join().ok())
+                .filter(|&won| won)
+                .count()
+        });
+        assert_eq!(winners, 1);
+        assert!(slot.load(Ordering::Acquire).is_valid());
+    }
+}
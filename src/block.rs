@@ -0,0 +1,302 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{index::KEYSPACE, TinyId};
+
+/// Splits the 48-bit keyspace (every valid [`TinyId`]) into contiguous [`Block`]s of a fixed
+/// size, and leases each block out exactly once.
+///
+/// This is meant for distributed systems: a single coordinator owns the allocator and hands a
+/// block to each worker, after which the worker can generate IDs from its block entirely locally
+/// while still being guaranteed never to collide with any other worker. The only state that needs
+/// to survive a coordinator restart is [`BlockAllocator::next_block`].
+///
+/// ## Example
+/// ```
+/// use tinyid::BlockAllocator;
+///
+/// let allocator = BlockAllocator::new(1_000);
+/// let mut worker_a = allocator.lease().unwrap();
+/// let mut worker_b = allocator.lease().unwrap();
+/// let a = worker_a.next().unwrap();
+/// let b = worker_b.next().unwrap();
+/// assert_ne!(a, b);
+/// assert!(worker_a.contains(a) && !worker_b.contains(a));
+/// ```
+#[derive(Debug)]
+pub struct BlockAllocator {
+    block_size: u64,
+    next: AtomicU64,
+}
+
+impl BlockAllocator {
+    /// Create a new [`BlockAllocator`] handing out blocks of `block_size` IDs, starting with the
+    /// first block. `block_size` is clamped to `1..=64^8`.
+    #[must_use]
+    pub fn new(block_size: u64) -> Self {
+        Self::resume(block_size, 0)
+    }
+
+    /// Create a [`BlockAllocator`] which continues leasing from block number `next_block`, e.g.
+    /// using the value of [`BlockAllocator::next_block`] saved before a restart.
+    #[must_use]
+    pub fn resume(block_size: u64, next_block: u64) -> Self {
+        Self {
+            block_size: block_size.clamp(1, KEYSPACE),
+            next: AtomicU64::new(next_block),
+        }
+    }
+
+    /// Leases the next unused block, or returns `None` if every block has been leased.
+    pub fn lease(&self) -> Option<Block> {
+        let count = self.block_count();
+        self.next
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |next| {
+                (next < count).then_some(next + 1)
+            })
+            .ok()
+            .map(|number| Block::new(number, self.block_size))
+    }
+
+    /// The number of IDs in each block.
+    #[must_use]
+    pub fn block_size(&self) -> u64 {
+        self.block_size
+    }
+
+    /// The total number of blocks in the keyspace. The last block may be smaller than the others
+    /// if the block size does not evenly divide `64^8`.
+    #[must_use]
+    pub fn block_count(&self) -> u64 {
+        KEYSPACE.div_ceil(self.block_size)
+    }
+
+    /// The number of the next block that will be leased.
+    #[must_use]
+    pub fn next_block(&self) -> u64 {
+        self.next.load(Ordering::Acquire).min(self.block_count())
+    }
+
+    /// The number of blocks which have not been leased yet.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.block_count() - self.next_block()
+    }
+}
+
+/// A contiguous range of the keyspace leased from a [`BlockAllocator`].
+///
+/// A block is an [`Iterator`] which yields each of its IDs in order, and can be serialized (with
+/// the `serde` feature) to send it to a worker or save a worker's progress.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BlockState")
+)]
+pub struct Block {
+    number: u64,
+    start: u64,
+    end: u64,
+    next: u64,
+}
+
+impl Block {
+    /// Create the block with the given number, for blocks of `block_size` IDs. This is what
+    /// [`BlockAllocator::lease`] returns, so it can be used to re-create a block that was leased
+    /// elsewhere.
+    #[must_use]
+    pub fn new(number: u64, block_size: u64) -> Self {
+        let block_size = block_size.clamp(1, KEYSPACE);
+        let start = number.saturating_mul(block_size).min(KEYSPACE);
+        let end = start.saturating_add(block_size).min(KEYSPACE);
+        Self {
+            number,
+            start,
+            end,
+            next: start,
+        }
+    }
+
+    /// The number of this block within its allocator.
+    #[must_use]
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The total number of IDs in this block.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// The number of IDs that have not been taken from this block yet.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.end.saturating_sub(self.next)
+    }
+
+    /// The first ID in this block.
+    #[must_use]
+    pub fn first_id(&self) -> TinyId {
+        TinyId::from_index_wrapping(self.start)
+    }
+
+    /// The last ID in this block.
+    #[must_use]
+    pub fn last_id(&self) -> TinyId {
+        TinyId::from_index_wrapping(self.end.saturating_sub(1))
+    }
+
+    /// Checks whether the given ID belongs to this block.
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        id.to_index()
+            .is_some_and(|index| (self.start..self.end).contains(&index))
+    }
+}
+
+/// The serialized form of a [`Block`], which is checked for consistency before it is used.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Block")]
+struct BlockState {
+    number: u64,
+    start: u64,
+    end: u64,
+    next: u64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BlockState> for Block {
+    type Error = &'static str;
+
+    fn try_from(state: BlockState) -> Result<Self, Self::Error> {
+        if !(state.start <= state.next && state.next <= state.end && state.end <= KEYSPACE) {
+            return Err("Block must have start <= next <= end <= 64^8");
+        }
+        Ok(Self {
+            number: state.number,
+            start: state.start,
+            end: state.end,
+            next: state.next,
+        })
+    }
+}
+
+impl Iterator for Block {
+    type Item = TinyId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let id = TinyId::from_index_wrapping(self.next);
+        self.next += 1;
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.remaining()).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Block {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn leasing() {
+        let allocator = BlockAllocator::new(KEYSPACE / 3);
+        assert_eq!(allocator.block_count(), 4);
+        let blocks: Vec<Block> = std::iter::from_fn(|| allocator.lease()).collect();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(allocator.remaining(), 0);
+        assert!(allocator.lease().is_none());
+        assert_eq!(allocator.next_block(), 4);
+        assert_eq!(blocks[0].first_id().to_string(), "--------");
+        assert_eq!(blocks[3].last_id().to_string(), "zzzzzzzz");
+        assert_eq!(blocks[3].size(), 1);
+        for pair in blocks.windows(2) {
+            assert_eq!(
+                pair[0].last_id().to_index().unwrap() + 1,
+                pair[1].first_id().to_index().unwrap()
+            );
+        }
+
+        let allocator = BlockAllocator::resume(10, 5);
+        assert_eq!(allocator.lease().unwrap().number(), 5);
+        assert_eq!(allocator.block_size(), 10);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn generating() {
+        let mut block = Block::new(1, 3);
+        assert_eq!(block.remaining(), 3);
+        assert_eq!(block.len(), 3);
+        let ids: Vec<TinyId> = block.by_ref().collect();
+        assert_eq!(
+            ids.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["-------2", "-------3", "-------4"]
+        );
+        assert!(ids.iter().all(|&id| block.contains(id)));
+        assert!(!block.contains(TinyId::from_index_wrapping(0)));
+        assert!(!block.contains(TinyId::null()));
+        assert_eq!(block.remaining(), 0);
+        assert_eq!(block.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let mut block = Block::new(2, 10);
+        block.next();
+        let json = serde_json::to_string(&block).unwrap();
+        assert_eq!(serde_json::from_str::<Block>(&json).unwrap(), block);
+
+        for bad in [
+            r#"{"number":0,"start":0,"end":5,"next":9}"#,
+            r#"{"number":0,"start":5,"end":0,"next":0}"#,
+            r#"{"number":0,"start":5,"end":9,"next":4}"#,
+            r#"{"number":0,"start":0,"end":281474976710657,"next":0}"#,
+        ] {
+            assert!(serde_json::from_str::<Block>(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn threads() {
+        use std::collections::HashSet;
+        let allocator = BlockAllocator::new(1_000);
+        let ids: HashSet<TinyId> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut ids = Vec::new();
+                        for _ in 0..4 {
+                            ids.extend(allocator.lease().unwrap());
+                        }
+                        ids
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+        assert_eq!(ids.len(), 8 * 4 * 1_000);
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{hash::mix64, IdProvider, TinyId, TinyIdError};
+
+/// How many candidates [`BloomUniqueGenerator::try_next_id`] will try before giving up.
+const MAX_ATTEMPTS: usize = 1_000;
+
+/// A generator which never returns the same [`TinyId`] twice, using a bloom filter instead of a
+/// [`HashSet`](std::collections::HashSet) to remember which IDs it has issued.
+///
+/// A bloom filter can only answer "definitely not issued" or "maybe issued", so candidates which
+/// *might* have been issued are thrown away and regenerated. This means the generator can never
+/// return a duplicate, but will occasionally reject an ID that was actually fine. The memory
+/// used is fixed up front by `expected_items` and `false_positive_rate`: roughly
+/// `-1.44 * log2(false_positive_rate)` bits per expected item (e.g. ~1.2 bytes per item at a 1%
+/// false positive rate, compared to well over 8 bytes per item for a `HashSet<TinyId>`).
+///
+/// Issuing more than `expected_items` IDs is allowed, but the false positive (and so retry) rate
+/// climbs as the filter fills up, until [`BloomUniqueGenerator::try_next_id`] eventually fails.
+///
+/// ## Example
+/// ```
+/// use tinyid::BloomUniqueGenerator;
+///
+/// let mut gen = BloomUniqueGenerator::new(10_000, 0.01);
+/// let a = gen.next_id();
+/// let b = gen.next_id();
+/// assert_ne!(a, b);
+/// assert!(gen.might_contain(a));
+/// assert_eq!(gen.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "BloomState")
+)]
+pub struct BloomUniqueGenerator {
+    bits: Vec<u64>,
+    bit_count: u64,
+    hash_count: u32,
+    len: usize,
+}
+
+impl BloomUniqueGenerator {
+    /// Create a new [`BloomUniqueGenerator`] sized so that, after issuing `expected_items` IDs,
+    /// a new candidate has a `false_positive_rate` chance of being needlessly rejected.
+    ///
+    /// `false_positive_rate` is clamped to the range `0.000_000_1..=0.5`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    #[must_use]
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let p = false_positive_rate.clamp(0.000_000_1, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bit_count = ((-n * p.ln() / (ln2 * ln2)).ceil() as u64).max(64);
+        let hash_count = ((bit_count as f64 / n) * ln2).round().clamp(1.0, 32.0) as u32;
+        Self {
+            bits: vec![0; bit_count.div_ceil(64) as usize],
+            bit_count,
+            hash_count,
+            len: 0,
+        }
+    }
+
+    /// Generate a new [`TinyId`] that this generator has never returned before.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the filter is so full that no unused ID could be
+    ///   found after many attempts.
+    pub fn try_next_id(&mut self) -> Result<TinyId, TinyIdError> {
+        for _ in 0..MAX_ATTEMPTS {
+            let id = TinyId::random();
+            if !self.might_contain(id) {
+                self.insert(id);
+                return Ok(id);
+            }
+        }
+        Err(TinyIdError::GenerationFailure)
+    }
+
+    /// Generate a new [`TinyId`] that this generator has never returned before.
+    ///
+    /// ## Panics
+    /// If the filter is too full to find an unused ID, see [`BloomUniqueGenerator::try_next_id`].
+    #[must_use]
+    pub fn next_id(&mut self) -> TinyId {
+        self.try_next_id()
+            .expect("BloomUniqueGenerator is too full to find an unused ID")
+    }
+
+    /// Checks whether the given ID *might* have been issued by this generator. A `false` result is
+    /// always correct, a `true` result may be a false positive.
+    #[must_use]
+    pub fn might_contain(&self, id: TinyId) -> bool {
+        self.bit_indices(id)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// The number of IDs this generator has issued.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether this generator has not issued any IDs yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The size of the filter, in bytes.
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Estimates the chance that the next candidate will be rejected as a (possible) duplicate,
+    /// given how many IDs have been issued so far.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn false_positive_rate(&self) -> f64 {
+        let k = f64::from(self.hash_count);
+        let fill = -k * self.len as f64 / self.bit_count as f64;
+        (1.0 - fill.exp()).powf(k)
+    }
+
+    /// Records `id` in the filter.
+    fn insert(&mut self, id: TinyId) {
+        for bit in self.bit_indices(id) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+        self.len += 1;
+    }
+
+    /// The filter bits used for `id`, using double hashing to derive `hash_count` indices from
+    /// two hashes.
+    #[allow(clippy::cast_possible_truncation)]
+    fn bit_indices(&self, id: TinyId) -> impl Iterator<Item = usize> {
+        let h1 = mix64(id.to_u64());
+        let h2 = mix64(h1) | 1;
+        let bit_count = self.bit_count;
+        (0..u64::from(self.hash_count))
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bit_count) as usize)
+    }
+}
+
+/// The serialized form of a [`BloomUniqueGenerator`], which is checked for consistency before it
+/// is used.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "BloomUniqueGenerator")]
+struct BloomState {
+    bits: Vec<u64>,
+    bit_count: u64,
+    hash_count: u32,
+    len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BloomState> for BloomUniqueGenerator {
+    type Error = &'static str;
+
+    fn try_from(state: BloomState) -> Result<Self, Self::Error> {
+        if state.bit_count == 0 {
+            return Err("BloomUniqueGenerator must have at least one bit");
+        }
+        if u64::try_from(state.bits.len()).ok() != Some(state.bit_count.div_ceil(64)) {
+            return Err("BloomUniqueGenerator bits do not match bit_count");
+        }
+        if !(1..=32).contains(&state.hash_count) {
+            return Err("BloomUniqueGenerator hash_count must be between 1 and 32");
+        }
+        Ok(Self {
+            bits: state.bits,
+            bit_count: state.bit_count,
+            hash_count: state.hash_count,
+            len: state.len,
+        })
+    }
+}
+
+impl IdProvider for BloomUniqueGenerator {
+    fn next_id(&mut self) -> TinyId {
+        BloomUniqueGenerator::next_id(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn basic_usage() {
+        let mut gen = BloomUniqueGenerator::new(1_000, 0.01);
+        assert!(gen.is_empty());
+        assert!(gen.memory_usage() < 2_000);
+        let ids: Vec<TinyId> = (0..1_000).map(|_| gen.next_id()).collect();
+        assert_eq!(gen.len(), 1_000);
+        assert!(ids.iter().all(|&id| id.is_valid() && gen.might_contain(id)));
+        assert!(gen.false_positive_rate() < 0.02);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn no_duplicates() {
+        use std::collections::HashSet;
+        let mut gen = BloomUniqueGenerator::new(100_000, 0.001);
+        let mut ids = HashSet::new();
+        for _ in 0..100_000 {
+            assert!(ids.insert(gen.next_id()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let mut gen = BloomUniqueGenerator::new(100, 0.01);
+        let id = gen.next_id();
+        let json = serde_json::to_string(&gen).unwrap();
+        let restored: BloomUniqueGenerator = serde_json::from_str(&json).unwrap();
+        assert!(restored.might_contain(id));
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.memory_usage(), gen.memory_usage());
+
+        for bad in [
+            r#"{"bits":[],"bit_count":0,"hash_count":1,"len":0}"#,
+            r#"{"bits":[0],"bit_count":65,"hash_count":1,"len":0}"#,
+            r#"{"bits":[0,0],"bit_count":64,"hash_count":1,"len":0}"#,
+            r#"{"bits":[0],"bit_count":64,"hash_count":0,"len":0}"#,
+            r#"{"bits":[0],"bit_count":64,"hash_count":33,"len":0}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<BloomUniqueGenerator>(bad).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn saturation() {
+        let mut gen = BloomUniqueGenerator::new(1, 0.5);
+        let mut result = Ok(TinyId::null());
+        for _ in 0..10_000 {
+            result = gen.try_next_id();
+            if result.is_err() {
+                break;
+            }
+        }
+        assert_eq!(result, Err(TinyIdError::GenerationFailure));
+    }
+}
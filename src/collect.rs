@@ -0,0 +1,121 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Building [`TinyId`]s from iterators of characters or bytes.
+//!
+//! [`TinyId::try_collect_chars`] and [`TinyId::try_collect_bytes`] require exactly 8 valid
+//! letters. Since [`FromIterator`] cannot fail, `collect::<TinyId>()` instead takes the first 8
+//! items (ignoring any more) and gives [`TinyId::null`] if there are fewer than 8 or any of them
+//! is not a valid letter.
+
+use crate::{TinyId, TinyIdError};
+
+impl TinyId {
+    /// Build a [`TinyId`] from exactly 8 valid letters.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there are fewer or more than 8 chars.
+    /// - [`TinyIdError::InvalidCharacters`] if any char is not a valid letter.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::try_collect_chars("ABCDEFGH".chars().map(|c| c.to_ascii_lowercase()));
+    /// assert_eq!(id, Ok(TinyId::from_str_unchecked("abcdefgh")));
+    ///
+    /// // `collect` truncates, and gives the null ID for anything invalid.
+    /// let id: TinyId = "abcdefghijk".chars().collect();
+    /// assert_eq!(id, "abcdefgh");
+    /// let id: TinyId = "abc".chars().collect();
+    /// assert!(id.is_null());
+    /// ```
+    pub fn try_collect_chars(chars: impl IntoIterator<Item = char>) -> Result<Self, TinyIdError> {
+        Self::try_collect_bytes(chars.into_iter().map(|ch| u8::try_from(ch).unwrap_or(0)))
+    }
+
+    /// Build a [`TinyId`] from exactly 8 valid letter bytes.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there are fewer or more than 8 bytes.
+    /// - [`TinyIdError::InvalidCharacters`] if any byte is not a valid letter.
+    pub fn try_collect_bytes(bytes: impl IntoIterator<Item = u8>) -> Result<Self, TinyIdError> {
+        let mut iter = bytes.into_iter();
+        let mut data = Self::NULL_DATA;
+        for b in &mut data {
+            *b = iter.next().ok_or(TinyIdError::InvalidLength)?;
+        }
+        if iter.next().is_some() {
+            return Err(TinyIdError::InvalidLength);
+        }
+        Self::from_bytes(data)
+    }
+}
+
+/// Takes the first 8 chars, giving [`TinyId::null`] if there are fewer or any is invalid. See
+/// [`TinyId::try_collect_chars`] for a strict version.
+impl FromIterator<char> for TinyId {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        Self::try_collect_chars(iter.into_iter().take(8)).unwrap_or_default()
+    }
+}
+
+/// Takes the first 8 bytes, giving [`TinyId::null`] if there are fewer or any is invalid. See
+/// [`TinyId::try_collect_bytes`] for a strict version.
+impl FromIterator<u8> for TinyId {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self::try_collect_bytes(iter.into_iter().take(8)).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn try_collect() {
+        let id = TinyId::random();
+        assert_eq!(TinyId::try_collect_chars(id.to_string().chars()), Ok(id));
+        assert_eq!(TinyId::try_collect_bytes(id.to_bytes()), Ok(id));
+        assert_eq!(
+            TinyId::try_collect_chars("abcdefg".chars()),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::try_collect_chars("abcdefghi".chars()),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::try_collect_chars("abcdéfgh".chars()),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::try_collect_bytes(*b"abc!efgh"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::try_collect_bytes(std::iter::empty()),
+            Err(TinyIdError::InvalidLength)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn collect() {
+        let id: TinyId = ('a'..='z').collect();
+        assert_eq!(id, "abcdefgh");
+        let id: TinyId = b"zyxwvuts".iter().copied().collect();
+        assert_eq!(id, "zyxwvuts");
+        let id: TinyId = "abc".chars().collect();
+        assert!(id.is_null());
+        let id: TinyId = "abc!efgh".bytes().collect();
+        assert!(id.is_null());
+        // Only the first 8 are looked at, so anything after them is ignored.
+        let id: TinyId = "abcdefgh!!!".chars().collect();
+        assert_eq!(id, "abcdefgh");
+    }
+}
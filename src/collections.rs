@@ -0,0 +1,527 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Collections and utilities for working with many [`TinyId`]s.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::{hash::mix64, TinyId};
+
+/// A [`Hasher`] specialized for [`TinyId`]s. An ID is already 8 bytes of (mostly) random data,
+/// so instead of running a general purpose hash function over it, its bytes are taken as a `u64`
+/// and passed through a single cheap mixing step.
+///
+/// Only use this for keys which are [`TinyId`]s; other types would hash poorly, and it is not
+/// resistant to deliberately colliding keys.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdHasher {
+    state: u64,
+}
+
+impl Hasher for IdHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state = self.state.rotate_left(8) ^ u64::from(b);
+        }
+    }
+
+    /// Length prefixes are the same for every [`TinyId`], so they are ignored.
+    fn write_usize(&mut self, _: usize) {}
+
+    fn finish(&self) -> u64 {
+        mix64(self.state)
+    }
+}
+
+/// A [`HashSet`] of [`TinyId`]s using [`IdHasher`].
+///
+/// ## Example
+/// ```
+/// use tinyid::{collections::IdHashSet, TinyId};
+///
+/// let mut set = IdHashSet::default();
+/// assert!(set.insert(TinyId::random()));
+/// ```
+pub type IdHashSet = HashSet<TinyId, BuildHasherDefault<IdHasher>>;
+
+/// A [`HashMap`] keyed by [`TinyId`]s using [`IdHasher`].
+pub type IdHashMap<V> = HashMap<TinyId, V, BuildHasherDefault<IdHasher>>;
+
+/// An ordered set of [`TinyId`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TinyIdSet {
+    ids: BTreeSet<TinyId>,
+}
+
+impl TinyIdSet {
+    /// Create a new, empty [`TinyIdSet`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `id` to the set, returning `true` if it was not already present.
+    pub fn insert(&mut self, id: TinyId) -> bool {
+        self.ids.insert(id)
+    }
+
+    /// Remove `id` from the set, returning `true` if it was present.
+    pub fn remove(&mut self, id: TinyId) -> bool {
+        self.ids.remove(&id)
+    }
+
+    /// Checks whether `id` is in the set.
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// The number of IDs in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks whether the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Remove all IDs from the set.
+    pub fn clear(&mut self) {
+        self.ids.clear();
+    }
+
+    /// Iterate over the IDs in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = TinyId> + '_ {
+        self.ids.iter().copied()
+    }
+
+    /// Find the IDs within `tolerance` edits of `query`, closest first. IDs at the same distance
+    /// are returned in ascending order. See [`TinyId::suggest`] for how distance is measured.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{collections::TinyIdSet, TinyId};
+    ///
+    /// let set = ["abcdefgh", "abcdefgX", "zzzzzzzz"]
+    ///     .into_iter()
+    ///     .map(TinyId::from_str_unchecked)
+    ///     .collect::<TinyIdSet>();
+    /// let found = set.fuzzy_find("abcdefgh", 1);
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].to_string(), "abcdefgh");
+    /// ```
+    #[must_use]
+    pub fn fuzzy_find(&self, query: &str, tolerance: usize) -> Vec<TinyId> {
+        TinyId::suggest(query, self.iter(), tolerance)
+    }
+}
+
+impl FromIterator<TinyId> for TinyIdSet {
+    fn from_iter<T: IntoIterator<Item = TinyId>>(iter: T) -> Self {
+        Self {
+            ids: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<TinyId> for TinyIdSet {
+    fn extend<T: IntoIterator<Item = TinyId>>(&mut self, iter: T) {
+        self.ids.extend(iter);
+    }
+}
+
+impl IntoIterator for TinyIdSet {
+    type Item = TinyId;
+    type IntoIter = std::collections::btree_set::IntoIter<TinyId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TinyIdSet {
+    type Item = TinyId;
+    type IntoIter = std::iter::Copied<std::collections::btree_set::Iter<'a, TinyId>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.iter().copied()
+    }
+}
+
+/// Maps [`TinyId`]s to dense `u32` handles and back, for graph algorithms and ECS-style systems
+/// which index arrays internally but use IDs at their API boundary. Handles are assigned in
+/// order of first insertion, starting from 0, so they can index a `Vec` directly.
+///
+/// IDs can not be removed, so handles stay valid for as long as the interner lives. With the
+/// `serde` feature, an interner is serialized as the list of its IDs in handle order.
+///
+/// ## Example
+/// ```
+/// use tinyid::{collections::IdInterner, TinyId};
+///
+/// let (a, b) = (TinyId::random(), TinyId::random());
+/// let mut interner = IdInterner::new();
+/// let mut edges = Vec::new();
+/// edges.push((interner.intern(a), interner.intern(b)));
+/// edges.push((interner.intern(b), interner.intern(a)));
+/// assert_eq!(edges, [(0, 1), (1, 0)]);
+/// assert_eq!(interner.resolve(1), Some(b));
+/// assert_eq!(interner.get(a), Some(0));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vec<TinyId>", into = "Vec<TinyId>")
+)]
+pub struct IdInterner {
+    ids: Vec<TinyId>,
+    handles: IdHashMap<u32>,
+}
+
+impl IdInterner {
+    /// Create a new, empty [`IdInterner`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty [`IdInterner`] with room for at least `capacity` IDs.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ids: Vec::with_capacity(capacity),
+            handles: IdHashMap::with_capacity_and_hasher(capacity, BuildHasherDefault::default()),
+        }
+    }
+
+    /// Get the handle for `id`, assigning it the next handle if it has not been interned yet.
+    ///
+    /// ## Panics
+    /// If `id` is new and all `2^32` handles have already been assigned.
+    pub fn intern(&mut self, id: TinyId) -> u32 {
+        if let Some(&handle) = self.handles.get(&id) {
+            return handle;
+        }
+        let handle = u32::try_from(self.ids.len()).expect("IdInterner has run out of handles");
+        self.ids.push(id);
+        self.handles.insert(id, handle);
+        handle
+    }
+
+    /// Get the handle for `id`, or `None` if it has not been interned.
+    #[must_use]
+    pub fn get(&self, id: TinyId) -> Option<u32> {
+        self.handles.get(&id).copied()
+    }
+
+    /// Get the ID with the given handle, or `None` if no ID has that handle.
+    #[must_use]
+    pub fn resolve(&self, handle: u32) -> Option<TinyId> {
+        self.ids.get(usize::try_from(handle).ok()?).copied()
+    }
+
+    /// Checks whether `id` has been interned.
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        self.handles.contains_key(&id)
+    }
+
+    /// The number of interned IDs, which is also the next handle to be assigned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks whether no IDs have been interned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The interned IDs, indexed by handle.
+    #[must_use]
+    pub fn ids(&self) -> &[TinyId] {
+        &self.ids
+    }
+
+    /// Iterate over each handle and its ID, in handle order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, TinyId)> + '_ {
+        (0..).zip(self.ids.iter().copied())
+    }
+}
+
+impl FromIterator<TinyId> for IdInterner {
+    fn from_iter<T: IntoIterator<Item = TinyId>>(iter: T) -> Self {
+        let mut interner = Self::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl Extend<TinyId> for IdInterner {
+    fn extend<T: IntoIterator<Item = TinyId>>(&mut self, iter: T) {
+        for id in iter {
+            self.intern(id);
+        }
+    }
+}
+
+impl From<Vec<TinyId>> for IdInterner {
+    fn from(ids: Vec<TinyId>) -> Self {
+        ids.into_iter().collect()
+    }
+}
+
+impl From<IdInterner> for Vec<TinyId> {
+    fn from(interner: IdInterner) -> Self {
+        interner.ids
+    }
+}
+
+/// Bucket `ids` by their first `len` characters, e.g. to shard an export or build a directory
+/// layout. Each bucket keeps its IDs in the order they were given, and only prefixes which occur
+/// are present. A `len` of 8 or more groups identical IDs, and a `len` of 0 puts every ID in the
+/// `""` bucket.
+///
+/// ## Example
+/// ```
+/// use tinyid::{collections::group_by_prefix, TinyId};
+///
+/// let ids = ["abcdefgh", "abzzzzzz", "xyz12345"].map(TinyId::from_str_unchecked);
+/// let groups = group_by_prefix(ids, 2);
+/// assert_eq!(groups.len(), 2);
+/// assert_eq!(groups["ab"], [ids[0], ids[1]]);
+/// assert_eq!(groups["xy"], [ids[2]]);
+/// ```
+#[must_use]
+pub fn group_by_prefix<I: IntoIterator<Item = TinyId>>(
+    ids: I,
+    len: usize,
+) -> HashMap<String, Vec<TinyId>> {
+    let len = len.min(8);
+    let mut groups: HashMap<String, Vec<TinyId>> = HashMap::new();
+    for id in ids {
+        let prefix = id.to_bytes()[..len].iter().map(|&b| b as char).collect();
+        groups.entry(prefix).or_default().push(id);
+    }
+    groups
+}
+
+/// Find the IDs which occur more than once in `ids`, e.g. to validate an import. Each duplicated ID
+/// is returned once, in the order its first repeat was seen.
+///
+/// ## Example
+/// ```
+/// use tinyid::{collections::find_duplicates, TinyId};
+///
+/// let [a, b, c] = ["aaaaaaaa", "bbbbbbbb", "cccccccc"].map(TinyId::from_str_unchecked);
+/// assert_eq!(find_duplicates([a, b, c, b, a, b]), [b, a]);
+/// assert!(find_duplicates([a, b, c]).is_empty());
+/// ```
+#[must_use]
+pub fn find_duplicates<I: IntoIterator<Item = TinyId>>(ids: I) -> Vec<TinyId> {
+    let mut seen = IdHashSet::default();
+    let mut reported = IdHashSet::default();
+    ids.into_iter()
+        .filter(|&id| !seen.insert(id) && reported.insert(id))
+        .collect()
+}
+
+/// Checks whether any ID occurs more than once in `ids`, stopping at the first repeat.
+///
+/// ## Example
+/// ```
+/// use tinyid::{collections::has_duplicates, TinyId};
+///
+/// let id = TinyId::random();
+/// assert!(has_duplicates([id, TinyId::random(), id]));
+/// assert!(!has_duplicates([id]));
+/// ```
+#[must_use]
+pub fn has_duplicates<I: IntoIterator<Item = TinyId>>(ids: I) -> bool {
+    let mut seen = IdHashSet::default();
+    ids.into_iter().any(|id| !seen.insert(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn set() {
+        let mut set = TinyIdSet::new();
+        assert!(set.is_empty());
+        let a = TinyId::from_str_unchecked("bbbbbbbb");
+        let b = TinyId::from_str_unchecked("aaaaaaaa");
+        assert!(set.insert(a));
+        assert!(set.insert(b));
+        assert!(!set.insert(a));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(a));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![b, a]);
+        assert_eq!((&set).into_iter().count(), 2);
+        assert!(set.remove(a));
+        assert!(!set.remove(a));
+        assert!(!set.contains(a));
+        set.extend([a, a]);
+        assert_eq!(set.clone().into_iter().collect::<Vec<_>>(), vec![b, a]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn fuzzy_find() {
+        let set = ["abcdeXYZ", "abcdefgh", "abcdefgZ", "abcdefgA", "12345678"]
+            .into_iter()
+            .map(TinyId::from_str_unchecked)
+            .collect::<TinyIdSet>();
+        let found = |query, tolerance| {
+            set.fuzzy_find(query, tolerance)
+                .into_iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(found("abcdefgh", 0), ["abcdefgh"]);
+        assert_eq!(found("abcdefgh", 1), ["abcdefgh", "abcdefgA", "abcdefgZ"]);
+        assert_eq!(
+            found("abcdefgh", 3),
+            ["abcdefgh", "abcdefgA", "abcdefgZ", "abcdeXYZ"]
+        );
+        assert!(found("zzzzzzzz", 2).is_empty());
+        assert!(TinyIdSet::new().fuzzy_find("abcdefgh", 8).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn interner() {
+        let ids = (0..1_000).map(|_| TinyId::random()).collect::<Vec<_>>();
+        let mut interner = IdInterner::with_capacity(ids.len());
+        assert!(interner.is_empty());
+        for (handle, &id) in (0..).zip(&ids) {
+            assert_eq!(interner.intern(id), handle);
+        }
+        for (handle, &id) in (0..).zip(&ids) {
+            assert_eq!(interner.intern(id), handle);
+            assert_eq!(interner.get(id), Some(handle));
+            assert_eq!(interner.resolve(handle), Some(id));
+        }
+        assert_eq!(interner.len(), ids.len());
+        assert_eq!(interner.ids(), ids);
+        assert!(interner
+            .iter()
+            .all(|(handle, id)| interner.get(id) == Some(handle)));
+        assert_eq!(interner.resolve(1_000), None);
+        assert_eq!(interner.get(TinyId::null()), None);
+        assert!(!interner.contains(TinyId::null()));
+
+        let (a, b) = (ids[0], ids[1]);
+        let interner: IdInterner = [a, b, a, b].into_iter().collect();
+        assert_eq!(interner.ids(), [a, b]);
+        assert_eq!(IdInterner::from(vec![a, b, a]), interner);
+        assert_eq!(Vec::from(interner), [a, b]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn interner_serde() {
+        let interner: IdInterner = (0..10).map(|_| TinyId::random()).collect();
+        let json = serde_json::to_string(&interner).unwrap();
+        assert_eq!(json, serde_json::to_string(interner.ids()).unwrap());
+        assert_eq!(serde_json::from_str::<IdInterner>(&json).unwrap(), interner);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn group_by_prefix() {
+        let ids = ["aaaaaaaa", "aabbbbbb", "abcccccc", "aaaaaaaa"].map(TinyId::from_str_unchecked);
+        let groups = super::group_by_prefix(ids, 1);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["a"], ids);
+
+        let groups = super::group_by_prefix(ids, 2);
+        assert_eq!(groups["aa"], [ids[0], ids[1], ids[3]]);
+        assert_eq!(groups["ab"], [ids[2]]);
+
+        let groups = super::group_by_prefix(ids, 100);
+        assert_eq!(groups["aaaaaaaa"].len(), 2);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(super::group_by_prefix(ids, 0)[""].len(), 4);
+        assert!(super::group_by_prefix([], 2).is_empty());
+
+        let odd = TinyId::from_bytes_unchecked(*b"\xffabcdefg");
+        assert_eq!(super::group_by_prefix([odd], 1)["\u{ff}"], [odd]);
+
+        let many = (0..10_000).map(|_| TinyId::random()).collect::<Vec<_>>();
+        let groups = super::group_by_prefix(many.iter().copied(), 1);
+        assert_eq!(groups.len(), TinyId::LETTER_COUNT);
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), many.len());
+        assert!(groups
+            .iter()
+            .all(|(prefix, ids)| ids.iter().all(|id| id.starts_with(prefix))));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn id_hasher() {
+        use std::hash::{BuildHasher, Hash};
+
+        let build = BuildHasherDefault::<IdHasher>::default();
+        let id = TinyId::random();
+        assert_eq!(build.hash_one(id), build.hash_one(id));
+        assert_ne!(build.hash_one(id), build.hash_one(id.wrapping_add(1)));
+
+        let mut hasher = IdHasher::default();
+        id.hash(&mut hasher);
+        assert_eq!(hasher.finish(), mix64(id.to_u64()));
+
+        let ids = (0..10_000).map(|_| TinyId::random()).collect::<Vec<_>>();
+        let set = ids.iter().copied().collect::<IdHashSet>();
+        assert_eq!(set.len(), ids.len());
+        assert!(ids.iter().all(|id| set.contains(id)));
+        let map = ids
+            .iter()
+            .map(|&id| (id, id.to_u64()))
+            .collect::<IdHashMap<_>>();
+        assert!(ids.iter().all(|id| map[id] == id.to_u64()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn duplicates() {
+        let ids = (0..1_000).map(|_| TinyId::random()).collect::<Vec<_>>();
+        assert!(!has_duplicates(ids.iter().copied()));
+        assert!(find_duplicates(ids.iter().copied()).is_empty());
+        assert!(!has_duplicates([]));
+
+        let mut with_dupes = ids.clone();
+        with_dupes.extend([ids[500], ids[3], ids[500], TinyId::null(), TinyId::null()]);
+        assert!(has_duplicates(with_dupes.iter().copied()));
+        assert_eq!(
+            find_duplicates(with_dupes),
+            [ids[500], ids[3], TinyId::null()]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let set = (0..10).map(|_| TinyId::random()).collect::<TinyIdSet>();
+        let json = serde_json::to_string(&set).unwrap();
+        assert!(json.starts_with('['));
+        assert_eq!(serde_json::from_str::<TinyIdSet>(&json).unwrap(), set);
+    }
+}
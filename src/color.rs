@@ -0,0 +1,113 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{hash::hash_bytes, TinyId};
+
+impl TinyId {
+    /// The saturation used by [`TinyId::to_color`].
+    pub const COLOR_SATURATION: f64 = 0.65;
+    /// The lightness used by [`TinyId::to_color`].
+    pub const COLOR_LIGHTNESS: f64 = 0.5;
+
+    /// Get a hue (in degrees, `0..360`) derived from this ID, the basis of
+    /// [`TinyId::to_color`]. The same ID always has the same hue, across processes and releases.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn hue(&self) -> u16 {
+        ((hash_bytes(&self.data) >> 32) % 360) as u16
+    }
+
+    /// Get an RGB color derived from this ID, for consistently color-coding entities across
+    /// dashboards and logs. See [`TinyId::to_color_hsl`] to control saturation and lightness,
+    /// e.g. for readable colors on dark or light backgrounds.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// let (r, g, b) = id.to_color();
+    /// assert_eq!((r, g, b), id.to_color());
+    /// println!("\x1b[38;2;{r};{g};{b}m{id}\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn to_color(&self) -> (u8, u8, u8) {
+        self.to_color_hsl(Self::COLOR_SATURATION, Self::COLOR_LIGHTNESS)
+    }
+
+    /// Get an RGB color with this ID's [`hue`](TinyId::hue) and the given `saturation` and
+    /// `lightness` (each clamped to `0.0..=1.0`).
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// // Lighter colors for a dark theme.
+    /// let (r, g, b) = id.to_color_hsl(0.7, 0.75);
+    /// assert!(u16::from(r) + u16::from(g) + u16::from(b) > 255);
+    /// ```
+    #[must_use]
+    pub fn to_color_hsl(&self, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+        hsl_to_rgb(
+            f64::from(self.hue()),
+            saturation.clamp(0.0, 1.0),
+            lightness.clamp(0.0, 1.0),
+        )
+    }
+}
+
+/// Convert an HSL color (hue in degrees, saturation and lightness from 0 to 1) to RGB.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::many_single_char_names
+)]
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - c / 2.0;
+    let (r, g, b) = match h {
+        h if h < 60.0 => (c, x, 0.0),
+        h if h < 120.0 => (x, c, 0.0),
+        h if h < 180.0 => (0.0, c, x),
+        h if h < 240.0 => (0.0, x, c),
+        h if h < 300.0 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let scale = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (scale(r), scale(g), scale(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn hsl() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 1.0), (255, 255, 255));
+        assert_eq!(hsl_to_rgb(300.0, 0.0, 0.0), (0, 0, 0));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn colors() {
+        // Colors must never change, they may be stored or shared.
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        assert_eq!(id.hue(), 241);
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert!(id.hue() < 360);
+            assert_eq!(id.to_color(), id.to_color());
+            assert_eq!(id.to_color_hsl(0.0, 1.0), (255, 255, 255));
+            assert_eq!(id.to_color_hsl(0.5, -1.0), (0, 0, 0));
+        }
+    }
+}
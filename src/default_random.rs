@@ -0,0 +1,104 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::TinyId;
+
+/// A [`TinyId`] wrapper whose [`Default`] is a new random ID instead of the null ID, for structs
+/// built with `..Default::default()` where a null ID is never wanted. It dereferences to the
+/// wrapped ID, and (with the `serde` feature) serializes exactly like it.
+///
+/// This is a wrapper rather than a feature flag so that changing the default is a local decision,
+/// and can't change the behavior of other crates using [`TinyId`] in the same build.
+///
+/// ## Example
+/// ```
+/// use tinyid::{DefaultRandom, TinyId};
+///
+/// #[derive(Default)]
+/// struct Order {
+///     id: DefaultRandom,
+///     note: String,
+/// }
+///
+/// let order = Order { note: "rush".into(), ..Default::default() };
+/// assert!(order.id.is_valid());
+/// let id: TinyId = order.id.into();
+/// assert_ne!(id, Order::default().id.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct DefaultRandom(pub TinyId);
+
+impl Default for DefaultRandom {
+    fn default() -> Self {
+        Self(TinyId::random())
+    }
+}
+
+impl std::ops::Deref for DefaultRandom {
+    type Target = TinyId;
+
+    fn deref(&self) -> &TinyId {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for DefaultRandom {
+    fn deref_mut(&mut self) -> &mut TinyId {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Display for DefaultRandom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<TinyId> for DefaultRandom {
+    fn from(id: TinyId) -> Self {
+        Self(id)
+    }
+}
+
+impl From<DefaultRandom> for TinyId {
+    fn from(wrapper: DefaultRandom) -> Self {
+        wrapper.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn default_is_random() {
+        let a = DefaultRandom::default();
+        let b = DefaultRandom::default();
+        assert!(a.is_valid() && b.is_valid());
+        assert_ne!(a, b);
+        assert_eq!(a.to_string(), a.0.to_string());
+        assert_eq!(TinyId::from(a), a.0);
+        assert_eq!(DefaultRandom::from(a.0), a);
+
+        let mut c = DefaultRandom::default();
+        c.make_null();
+        assert!(c.0.is_null());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde_transparent() {
+        let id = DefaultRandom::default();
+        assert_eq!(
+            serde_json::to_value(id).unwrap(),
+            serde_json::to_value(id.0).unwrap()
+        );
+    }
+}
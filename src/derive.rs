@@ -0,0 +1,68 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{hash::hash_bytes, TinyId};
+
+impl TinyId {
+    /// Deterministically derive the `index`th child of this [`TinyId`], by hashing this ID together
+    /// with `index`. Both ends of a protocol that know the parent can compute the same related IDs
+    /// without transmitting them, and the result never changes between releases.
+    ///
+    /// Children are always valid IDs, but are **not** guaranteed to be distinct from each other or
+    /// from other IDs, and the parent can not be recovered from a child. This is not
+    /// cryptographically secure: anyone who knows the parent can derive its children.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let parent = TinyId::from_str_unchecked("abcdefgh");
+    /// let child = parent.derive_child(0);
+    /// assert!(child.is_valid());
+    /// assert_eq!(child, parent.derive_child(0));
+    /// assert_ne!(child, parent.derive_child(1));
+    /// ```
+    #[must_use]
+    pub fn derive_child(&self, index: u64) -> TinyId {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.data);
+        bytes[8..].copy_from_slice(&index.to_be_bytes());
+        Self::from_index_wrapping(hash_bytes(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn derive_child() {
+        let parent = TinyId::from_str_unchecked("abcdefgh");
+        let children = (0..1000)
+            .map(|i| parent.derive_child(i))
+            .collect::<Vec<_>>();
+        assert!(children.iter().all(|id| id.is_valid()));
+        let unique = children
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        assert_eq!(unique, children.len());
+        assert_ne!(
+            TinyId::from_str_unchecked("abcdefgi").derive_child(0),
+            children[0]
+        );
+        assert!(TinyId::null().derive_child(0).is_valid());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn stable() {
+        // Derived IDs must never change, they may have been computed by another version.
+        let parent = TinyId::from_str_unchecked("abcdefgh");
+        assert_eq!(parent.derive_child(0).to_string(), "8Q2gv1X_");
+    }
+}
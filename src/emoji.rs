@@ -0,0 +1,114 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// One emoji for each 6-bit group, all single code points so they never need a variation
+/// selector. This table must never change, or previously shared emoji IDs would decode wrongly.
+const EMOJI: [char; 64] = [
+    '🐶', '🐱', '🐭', '🐹', '🐰', '🦊', '🐻', '🐼', '🐨', '🐯', '🦁', '🐮', '🐷', '🐸', '🐵', '🐔',
+    '🐧', '🐦', '🐤', '🦆', '🦅', '🦉', '🦇', '🐺', '🐗', '🐴', '🦄', '🐝', '🐛', '🦋', '🐌', '🐞',
+    '🐜', '🐢', '🐍', '🦎', '🐙', '🦑', '🦀', '🐡', '🐠', '🐟', '🐬', '🐳', '🦈', '🐊', '🐅', '🐆',
+    '🦓', '🦍', '🐘', '🦏', '🐪', '🦒', '🦘', '🐃', '🍎', '🍐', '🍊', '🍋', '🍌', '🍉', '🍇', '🍓',
+];
+
+impl TinyId {
+    /// Convert this [`TinyId`] to eight emoji, a compact visual form for sharing in chat. Returns
+    /// `None` if this ID is not valid.
+    ///
+    /// Each emoji encodes 6 of the ID's 48 bits using a fixed table, and
+    /// [`TinyId::from_emoji`] converts back.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let emoji = id.to_emoji().unwrap();
+    /// assert_eq!(emoji.chars().count(), 8);
+    /// assert_eq!(TinyId::from_emoji(&emoji), Ok(id));
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn to_emoji(self) -> Option<String> {
+        let index = self.to_index()?;
+        Some(
+            (0..8)
+                .rev()
+                .map(|i| EMOJI[(index >> (i * 6) & 0x3F) as usize])
+                .collect(),
+        )
+    }
+
+    /// Convert eight emoji created by [`TinyId::to_emoji`] back to a [`TinyId`]. Whitespace
+    /// between the emoji is ignored.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there are not exactly eight emoji.
+    /// - [`TinyIdError::InvalidCharacters`] if any character is not one of the emoji.
+    pub fn from_emoji(emoji: &str) -> Result<Self, TinyIdError> {
+        let mut count = 0;
+        let mut index = 0u64;
+        for ch in emoji.chars().filter(|c| !c.is_whitespace()) {
+            let value = EMOJI
+                .iter()
+                .position(|&e| e == ch)
+                .ok_or(TinyIdError::InvalidCharacters)?;
+            index = index << 6 | value as u64;
+            count += 1;
+        }
+        if count != 8 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        Ok(Self::from_index_wrapping(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn table() {
+        let unique: std::collections::HashSet<char> = EMOJI.into_iter().collect();
+        assert_eq!(unique.len(), EMOJI.len());
+        assert!(EMOJI.iter().all(|e| e.len_utf8() == 4));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn roundtrip() {
+        for _ in 0..1_000 {
+            let id = TinyId::random();
+            let emoji = id.to_emoji().unwrap();
+            assert_eq!(TinyId::from_emoji(&emoji), Ok(id));
+            let spaced = emoji.chars().flat_map(|c| [c, ' ']).collect::<String>();
+            assert_eq!(TinyId::from_emoji(&spaced), Ok(id));
+        }
+        assert_eq!(
+            TinyId::from_index_wrapping(0).to_emoji().unwrap(),
+            "🐶🐶🐶🐶🐶🐶🐶🐶"
+        );
+        assert_eq!(
+            TinyId::from_str_unchecked("zzzzzzzz").to_emoji().unwrap(),
+            "🍓🍓🍓🍓🍓🍓🍓🍓"
+        );
+        assert_eq!(TinyId::null().to_emoji(), None);
+        assert_eq!(
+            TinyId::from_emoji("🐶🐶🐶"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_emoji("🐶🐶🐶🐶🐶🐶🐶🐶🐶"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_emoji("🐶🐶🐶🐶🐶🐶🐶a"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
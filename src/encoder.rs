@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{feistel::Feistel48, hash::hash_bytes, index::KEYSPACE, TinyId};
+
+/// A [Hashids](https://hashids.org)-style encoder which turns integers (e.g. sequential database
+/// keys) into opaque-looking [`TinyId`]s and back again, using a per-application salt.
+///
+/// Every integer below `2^48` encodes to a different ID, and different salts produce unrelated
+/// encodings. Like Hashids, this is obfuscation rather than encryption: it stops casual
+/// enumeration and hides volume, but should not be relied on to keep the integers secret.
+///
+/// Unlike Hashids, *every* valid ID decodes to some integer, so [`Encoder::decode`] can't tell
+/// whether an ID was actually produced by [`Encoder::encode`]; look the decoded key up as usual.
+///
+/// ## Example
+/// ```
+/// use tinyid::Encoder;
+///
+/// let encoder = Encoder::new("my application salt");
+/// let id = encoder.encode(1).unwrap();
+/// assert_eq!(encoder.decode(id), Some(1));
+/// assert_ne!(Encoder::new("another salt").encode(1), Some(id));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Encoder {
+    cipher: Feistel48,
+}
+
+impl Encoder {
+    /// Create a new [`Encoder`] using the given salt.
+    #[must_use]
+    pub fn new(salt: impl AsRef<[u8]>) -> Self {
+        Self {
+            cipher: Feistel48::new(hash_bytes(salt.as_ref())),
+        }
+    }
+
+    /// Encode `n` as a [`TinyId`], or return `None` if `n` is not less than `2^48`.
+    #[must_use]
+    pub fn encode(&self, n: u64) -> Option<TinyId> {
+        (n < KEYSPACE).then(|| TinyId::from_index_wrapping(self.cipher.permute(n)))
+    }
+
+    /// Decode an ID produced by [`Encoder::encode`], or return `None` if `id` is not valid.
+    #[must_use]
+    pub fn decode(&self, id: TinyId) -> Option<u64> {
+        id.to_index().map(|index| self.cipher.invert(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn roundtrip() {
+        let encoder = Encoder::new("salt");
+        let mut previous = None;
+        for n in (0..1_000).chain([KEYSPACE - 1]) {
+            let id = encoder.encode(n).unwrap();
+            assert!(id.is_valid());
+            assert_eq!(encoder.decode(id), Some(n));
+            assert_ne!(previous, Some(id));
+            previous = Some(id);
+        }
+        assert_eq!(encoder.encode(KEYSPACE), None);
+        assert_eq!(encoder.decode(TinyId::null()), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn salts() {
+        let a = Encoder::new("a");
+        let b = Encoder::new(b"b");
+        assert_eq!(a, Encoder::new(String::from("a")));
+        assert_ne!(a.encode(5), b.encode(5));
+        assert_ne!(b.decode(a.encode(5).unwrap()), Some(5));
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    hash::{mix64, GAMMA},
+    index::KEYSPACE,
+    TinyId,
+};
+
+/// The number of rounds used by [`Feistel48`].
+const ROUNDS: usize = 6;
+/// Mask for one 24-bit half of a 48-bit block.
+const HALF_MASK: u64 = (1 << 24) - 1;
+
+/// A keyed, balanced Feistel network over 48-bit integers, i.e. a pseudo-random permutation of
+/// the keyspace indices `0..64^8`.
+///
+/// This is **not** a secure block cipher, it only needs to scramble indices well enough that
+/// consecutive inputs produce unrelated-looking outputs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Feistel48 {
+    round_keys: [u64; ROUNDS],
+}
+
+impl Feistel48 {
+    /// Create a new permutation from the given key.
+    pub(crate) fn new(key: u64) -> Self {
+        let mut round_keys = [0; ROUNDS];
+        let mut state = key;
+        for round_key in &mut round_keys {
+            state = mix64(state.wrapping_add(GAMMA));
+            *round_key = state;
+        }
+        Self { round_keys }
+    }
+
+    /// Permutes `index`, which must be less than `64^8`.
+    pub(crate) fn permute(&self, index: u64) -> u64 {
+        let (mut left, mut right) = (index >> 24 & HALF_MASK, index & HALF_MASK);
+        for &key in &self.round_keys {
+            (left, right) = (right, left ^ Self::round(right, key));
+        }
+        left << 24 | right
+    }
+
+    /// Reverses [`Feistel48::permute`].
+    pub(crate) fn invert(&self, index: u64) -> u64 {
+        let (mut left, mut right) = (index >> 24 & HALF_MASK, index & HALF_MASK);
+        for &key in self.round_keys.iter().rev() {
+            (left, right) = (right ^ Self::round(left, key), left);
+        }
+        left << 24 | right
+    }
+
+    /// The round function, mixing one 24-bit half with a round key.
+    fn round(half: u64, key: u64) -> u64 {
+        mix64(half ^ key) & HALF_MASK
+    }
+}
+
+/// A generator which walks the *entire* keyspace (all `64^8` valid [`TinyId`]s) in a
+/// pseudo-random order determined by its key, without ever repeating an ID.
+///
+/// Internally it just increments a counter and scrambles it with a keyed permutation, so it uses
+/// a fixed (tiny) amount of memory no matter how many IDs it has produced, and needs no
+/// [`HashSet`](std::collections::HashSet) to guarantee uniqueness. Two generators with the same key
+/// produce the same sequence, and [`FeistelGenerator::position`] together with
+/// [`FeistelGenerator::resume`] can be used to continue a sequence after a restart.
+///
+/// The order is only *pseudo*-random: anyone who learns the key can predict every ID.
+///
+/// With the `serde` feature, only the key and position are saved.
+///
+/// ## Example
+/// ```
+/// use tinyid::FeistelGenerator;
+///
+/// let mut gen = FeistelGenerator::new(0xDEAD_BEEF);
+/// let first: Vec<_> = gen.by_ref().take(3).collect();
+/// let mut resumed = FeistelGenerator::resume(0xDEAD_BEEF, gen.position());
+/// assert_eq!(gen.next(), resumed.next());
+/// assert_eq!(first, FeistelGenerator::new(0xDEAD_BEEF).take(3).collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "FeistelState", into = "FeistelState")
+)]
+pub struct FeistelGenerator {
+    cipher: Feistel48,
+    key: u64,
+    counter: u64,
+}
+
+impl FeistelGenerator {
+    /// Create a new [`FeistelGenerator`] whose order is determined by `key`.
+    #[must_use]
+    pub fn new(key: u64) -> Self {
+        Self::resume(key, 0)
+    }
+
+    /// Create a new [`FeistelGenerator`] with a random key.
+    #[must_use]
+    pub fn random() -> Self {
+        Self::new(crate::rng::u64())
+    }
+
+    /// Create a [`FeistelGenerator`] with the given key which has already produced `position`
+    /// IDs.
+    #[must_use]
+    pub fn resume(key: u64, position: u64) -> Self {
+        Self {
+            cipher: Feistel48::new(key),
+            key,
+            counter: position.min(KEYSPACE),
+        }
+    }
+
+    /// The key which determines the order of this generator.
+    #[must_use]
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    /// The number of IDs this generator has produced so far.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.counter
+    }
+
+    /// Get the position in this generator's sequence at which `id` is (or was) produced, or
+    /// `None` if `id` is not valid. Only depends on the key, not on the current position.
+    #[must_use]
+    pub fn position_of(&self, id: TinyId) -> Option<u64> {
+        id.to_index().map(|index| self.cipher.invert(index))
+    }
+
+    /// The number of IDs this generator can still produce before the keyspace is exhausted.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        KEYSPACE - self.counter
+    }
+}
+
+/// The serialized form of a [`FeistelGenerator`]: its key and position, from which the
+/// permutation is rebuilt.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "FeistelGenerator")]
+struct FeistelState {
+    key: u64,
+    counter: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<FeistelGenerator> for FeistelState {
+    fn from(gen: FeistelGenerator) -> Self {
+        Self {
+            key: gen.key,
+            counter: gen.counter,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<FeistelState> for FeistelGenerator {
+    type Error = &'static str;
+
+    fn try_from(state: FeistelState) -> Result<Self, Self::Error> {
+        if state.counter > KEYSPACE {
+            return Err("FeistelGenerator counter is past the end of the keyspace");
+        }
+        Ok(Self::resume(state.key, state.counter))
+    }
+}
+
+impl Iterator for FeistelGenerator {
+    type Item = TinyId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.counter >= KEYSPACE {
+            return None;
+        }
+        let id = TinyId::from_index_wrapping(self.cipher.permute(self.counter));
+        self.counter += 1;
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.remaining()).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+impl TinyId {
+    /// Hide a monotonically increasing `counter` (e.g. a database row number) behind a keyed
+    /// permutation, so it can be exposed publicly without revealing how many rows exist or letting
+    /// anyone enumerate them. Every counter maps to a different ID, and
+    /// [`TinyId::deobfuscate_counter`] with the same key recovers it.
+    ///
+    /// This is the same mapping used by [`FeistelGenerator`], i.e. the ID returned by a generator
+    /// with key `key` at position `counter`. Returns `None` if `counter` is not less than `64^8`.
+    ///
+    /// This hides volume from casual observers but is **not** encryption; keep `key` secret and
+    /// don't rely on it where real security is needed.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// const KEY: u64 = 0x5EC2_E7;
+    /// let id = TinyId::from_counter_obfuscated(42, KEY).unwrap();
+    /// assert_ne!(TinyId::from_counter_obfuscated(43, KEY), Some(id));
+    /// assert_eq!(id.deobfuscate_counter(KEY), Some(42));
+    /// ```
+    #[must_use]
+    pub fn from_counter_obfuscated(counter: u64, key: u64) -> Option<Self> {
+        (counter < KEYSPACE)
+            .then(|| Self::from_index_wrapping(Feistel48::new(key).permute(counter)))
+    }
+
+    /// Recover the counter passed to [`TinyId::from_counter_obfuscated`] with the same `key`, or
+    /// `None` if this ID is not valid.
+    #[must_use]
+    pub fn deobfuscate_counter(self, key: u64) -> Option<u64> {
+        self.to_index()
+            .map(|index| Feistel48::new(key).invert(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn permutation_roundtrip() {
+        let cipher = Feistel48::new(42);
+        for index in (0..10_000).chain([KEYSPACE - 1, KEYSPACE / 2]) {
+            let permuted = cipher.permute(index);
+            assert!(permuted < KEYSPACE);
+            assert_eq!(cipher.invert(permuted), index);
+        }
+        assert_ne!(Feistel48::new(1).permute(0), Feistel48::new(2).permute(0));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn obfuscated_counter() {
+        let key = 0x1234_5678;
+        let ids: Vec<TinyId> = (0..100)
+            .map(|n| TinyId::from_counter_obfuscated(n, key).unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            FeistelGenerator::new(key).take(100).collect::<Vec<_>>()
+        );
+        for (n, id) in (0..).zip(&ids) {
+            assert!(id.is_valid());
+            assert_eq!(id.deobfuscate_counter(key), Some(n));
+            assert_ne!(id.deobfuscate_counter(key + 1), Some(n));
+        }
+        assert!(TinyId::from_counter_obfuscated(KEYSPACE - 1, key).is_some());
+        assert_eq!(TinyId::from_counter_obfuscated(KEYSPACE, key), None);
+        assert_eq!(TinyId::null().deobfuscate_counter(key), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn no_repeats() {
+        use std::collections::HashSet;
+        let ids: HashSet<TinyId> = FeistelGenerator::random().take(1_000_000).collect();
+        assert_eq!(ids.len(), 1_000_000);
+        assert!(ids.iter().all(|id| id.is_valid()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn exhaustion() {
+        let mut gen = FeistelGenerator::resume(7, KEYSPACE - 2);
+        assert_eq!(gen.key(), 7);
+        assert_eq!(gen.remaining(), 2);
+        assert_eq!(gen.size_hint(), (2, Some(2)));
+        assert!(gen.next().is_some());
+        assert!(gen.next().is_some());
+        assert_eq!(gen.next(), None);
+        assert_eq!(gen.position(), KEYSPACE);
+
+        let mut gen = FeistelGenerator::new(7);
+        let third = gen.nth(2).unwrap();
+        assert_eq!(gen.position_of(third), Some(2));
+        assert_eq!(gen.position_of(TinyId::null()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let mut gen = FeistelGenerator::new(7);
+        gen.next();
+        let json = serde_json::to_string(&gen).unwrap();
+        assert_eq!(json, r#"{"key":7,"counter":1}"#);
+        let mut restored: FeistelGenerator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, gen);
+        assert_eq!(restored.next(), gen.next());
+
+        let past_end = format!(r#"{{"key":7,"counter":{}}}"#, KEYSPACE + 1);
+        assert!(serde_json::from_str::<FeistelGenerator>(&past_end).is_err());
+        let end = format!(r#"{{"key":7,"counter":{KEYSPACE}}}"#);
+        assert_eq!(
+            serde_json::from_str::<FeistelGenerator>(&end)
+                .unwrap()
+                .remaining(),
+            0
+        );
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Alternative ways of displaying [`TinyId`]s.
+
+use crate::{TinyId, TinyIdError, TinyIdStr};
+
+/// Displays a [`TinyId`] split into chunks, like a license key (e.g. `ab-cd-ef-gh` or
+/// `abcd efgh`). Created by [`TinyId::display_chunked`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Chunked<'a> {
+    id: TinyId,
+    chunk_size: usize,
+    separator: &'a str,
+}
+
+impl std::fmt::Display for Chunked<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, ch) in self.id.to_bytes().iter().enumerate() {
+            if i > 0 && self.chunk_size > 0 && i % self.chunk_size == 0 {
+                f.write_str(self.separator)?;
+            }
+            write!(f, "{}", *ch as char)?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays a [`TinyId`] that may not be valid, writing [`Checked::REPLACEMENT`] in place of each
+/// byte that is not a valid letter instead of the raw byte. The null ID displays as `????????`
+/// rather than eight NUL bytes. Created by [`TinyId::display_checked`] and
+/// [`TinyId::try_display`].
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// let id = TinyId::from_bytes_unchecked(*b"abc\x07efgh");
+/// assert_eq!(id.display_checked().to_string(), "abc?efgh");
+/// assert_eq!(TinyId::null().display_checked().to_string(), "????????");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Checked {
+    id: TinyId,
+}
+
+impl Checked {
+    /// The character written in place of each invalid byte.
+    pub const REPLACEMENT: char = '?';
+}
+
+impl std::fmt::Display for Checked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for &ch in &self.id.data {
+            if TinyId::is_valid_byte(ch) {
+                write!(f, "{}", ch as char)?;
+            } else {
+                write!(f, "{}", Self::REPLACEMENT)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays a [`TinyId`], or a chosen piece of text if it is the null ID, since printing eight NUL
+/// bytes breaks terminals and log processors. Created by [`TinyId::display_null_as`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NullAs<'a> {
+    id: TinyId,
+    null: &'a str,
+}
+
+impl std::fmt::Display for NullAs<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.id.is_null() {
+            f.write_str(self.null)
+        } else {
+            std::fmt::Display::fmt(&self.id, f)
+        }
+    }
+}
+
+/// A [`TinyId`] along with its string form, computed once up front so displaying it (or
+/// borrowing it as a `&str`) is just a copy. Created by [`TinyId::display_cached`], for render
+/// loops and templates which print the same IDs over and over.
+///
+/// The string form is a [`TinyIdStr`], so it is stored inline and never allocates.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// let id = TinyId::random();
+/// let cached = id.display_cached();
+/// assert_eq!(cached.id(), id);
+/// assert_eq!(cached.as_str(), id.to_string());
+/// assert_eq!(format!("{cached}"), id.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DisplayCached {
+    id: TinyId,
+    text: TinyIdStr,
+}
+
+impl DisplayCached {
+    /// Cache the string form of `id`.
+    #[must_use]
+    pub fn new(id: TinyId) -> Self {
+        Self {
+            id,
+            text: id.to_str_buf(),
+        }
+    }
+
+    /// The cached [`TinyId`].
+    #[must_use]
+    pub fn id(&self) -> TinyId {
+        self.id
+    }
+
+    /// The cached string form.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.text.as_str()
+    }
+}
+
+impl From<TinyId> for DisplayCached {
+    fn from(id: TinyId) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<DisplayCached> for TinyId {
+    fn from(cached: DisplayCached) -> Self {
+        cached.id
+    }
+}
+
+impl std::ops::Deref for DisplayCached {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for DisplayCached {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for DisplayCached {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Format `ids` as a numbered table with `columns` IDs per row, e.g. for admin CLIs presenting a
+/// pick-list of IDs. Entries are numbered from 1 as `#001: abcdefgh`, with the numbers padded to
+/// at least three digits, separated by ` | ` and each row ending in a newline. A `columns` of `0`
+/// is treated as `1`. IDs are written with [`TinyId::display_checked`], so invalid IDs never put
+/// raw NUL or control bytes into the output.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// let ids = ["aaaaaaaa", "bbbbbbbb", "cccccccc"].map(TinyId::from_str_unchecked);
+/// assert_eq!(
+///     tinyid::fmt::table(ids, 2),
+///     "#001: aaaaaaaa | #002: bbbbbbbb\n#003: cccccccc\n"
+/// );
+/// ```
+#[must_use]
+pub fn table<I: IntoIterator<Item = TinyId>>(ids: I, columns: usize) -> String {
+    use std::fmt::Write;
+
+    let ids: Vec<TinyId> = ids.into_iter().collect();
+    let columns = columns.max(1);
+    let width = ids.len().to_string().len().max(3);
+    let mut out = String::new();
+    for (i, id) in ids.iter().enumerate() {
+        if i % columns > 0 {
+            out.push_str(" | ");
+        }
+        // Writing to a `String` can not fail.
+        let _ = write!(out, "#{:0width$}: {}", i + 1, id.display_checked());
+        if (i + 1) % columns == 0 || i + 1 == ids.len() {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+impl TinyId {
+    /// Display this [`TinyId`] split into chunks of `chunk_size` characters, separated by
+    /// `separator`. A `chunk_size` of `0` (or `8` or more) displays the ID in one piece.
+    /// [`TinyId::parse_chunked`] parses the result back.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert_eq!(id.display_chunked(2, "-").to_string(), "ab-cd-ef-gh");
+    /// assert_eq!(id.display_chunked(4, " ").to_string(), "abcd efgh");
+    /// assert_eq!(id.display_chunked(3, "-").to_string(), "abc-def-gh");
+    /// ```
+    #[must_use]
+    pub fn display_chunked(self, chunk_size: usize, separator: &str) -> Chunked<'_> {
+        Chunked {
+            id: self,
+            chunk_size,
+            separator,
+        }
+    }
+
+    /// Display this [`TinyId`] without ever writing invalid bytes, e.g. for logging IDs that may be
+    /// null or were built with an unchecked constructor. See [`Checked`].
+    #[must_use]
+    pub fn display_checked(self) -> Checked {
+        Checked { id: self }
+    }
+
+    /// Display this [`TinyId`], writing `null` instead if it is the null ID. Common choices are
+    /// `"(null)"`, `"--------"` or `""`; any other ID is displayed as usual. See [`NullAs`].
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let null = TinyId::null();
+    /// assert_eq!(null.display_null_as("(null)").to_string(), "(null)");
+    /// assert_eq!(null.display_null_as("--------").to_string(), "--------");
+    /// assert_eq!(null.display_null_as("").to_string(), "");
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert_eq!(id.display_null_as("(null)").to_string(), "abcdefgh");
+    /// ```
+    #[must_use]
+    pub fn display_null_as(self, null: &str) -> NullAs<'_> {
+        NullAs { id: self, null }
+    }
+
+    /// Display this [`TinyId`] only if it is valid, so the plain string form can be written
+    /// without risking NUL or control bytes.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if this ID is null or contains invalid bytes.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert_eq!(id.try_display().unwrap().to_string(), "abcdefgh");
+    /// assert!(TinyId::null().try_display().is_err());
+    /// ```
+    pub fn try_display(self) -> Result<Checked, TinyIdError> {
+        if self.is_valid() {
+            Ok(self.display_checked())
+        } else {
+            Err(TinyIdError::InvalidCharacters)
+        }
+    }
+
+    /// Compute the string form of this [`TinyId`] once, for displaying it many times. See
+    /// [`DisplayCached`].
+    #[must_use]
+    pub fn display_cached(self) -> DisplayCached {
+        DisplayCached::new(self)
+    }
+
+    /// Parse a [`TinyId`] displayed with [`TinyId::display_chunked`] using the same
+    /// `chunk_size` and `separator`. The separator is only expected between chunks, so
+    /// separators that are also valid ID characters (such as `-`) are handled correctly.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if the input has too few or too many characters.
+    /// - [`TinyIdError::InvalidCharacters`] if a separator is missing or the input contains
+    ///   characters that are not valid in a [`TinyId`].
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::parse_chunked("ab-cd-ef-gh", 2, "-").unwrap();
+    /// assert_eq!(id.to_string(), "abcdefgh");
+    /// ```
+    pub fn parse_chunked(s: &str, chunk_size: usize, separator: &str) -> Result<Self, TinyIdError> {
+        let mut bytes = [0u8; 8];
+        let mut rest = s;
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            if i > 0 && chunk_size > 0 && i % chunk_size == 0 {
+                if rest.is_empty() {
+                    return Err(TinyIdError::InvalidLength);
+                }
+                rest = rest
+                    .strip_prefix(separator)
+                    .ok_or(TinyIdError::InvalidCharacters)?;
+            }
+            let mut chars = rest.chars();
+            let ch = chars.next().ok_or(TinyIdError::InvalidLength)?;
+            *byte = u8::try_from(ch).map_err(|_| TinyIdError::InvalidCharacters)?;
+            rest = chars.as_str();
+        }
+        if !rest.is_empty() {
+            return Err(TinyIdError::InvalidLength);
+        }
+        Self::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn chunked() {
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        assert_eq!(id.display_chunked(0, "-").to_string(), "abcdefgh");
+        assert_eq!(id.display_chunked(8, "-").to_string(), "abcdefgh");
+        assert_eq!(id.display_chunked(1, "").to_string(), "abcdefgh");
+        assert_eq!(id.display_chunked(4, " - ").to_string(), "abcd - efgh");
+
+        for (size, sep) in [(1, "."), (2, "-"), (4, " "), (4, " - "), (8, "")] {
+            let id = TinyId::random();
+            let text = id.display_chunked(size, sep).to_string();
+            assert_eq!(TinyId::parse_chunked(&text, size, sep), Ok(id));
+        }
+        assert_eq!(
+            TinyId::parse_chunked("a--bc--d---", 2, "-"),
+            Ok(TinyId::from_str_unchecked("a-bc-d--"))
+        );
+        assert_eq!(
+            TinyId::parse_chunked("ab-cd-ef", 2, "-"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::parse_chunked("ab-cd-ef-gh-", 2, "-"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::parse_chunked("ab cd ef gh", 2, "-"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::parse_chunked("ab-cd-éf-gh", 2, "-"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn display_cached() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            let cached = id.display_cached();
+            assert_eq!(cached.id(), id);
+            assert_eq!(TinyId::from(cached), id);
+            assert_eq!(DisplayCached::from(id), cached);
+            assert_eq!(&*cached, id.to_string());
+            assert_eq!(cached.to_string(), id.to_string());
+            let s: &str = cached.as_ref();
+            assert_eq!(s, id.to_string());
+        }
+        let a = TinyId::from_str_unchecked("aaaaaaaa").display_cached();
+        let b = TinyId::from_str_unchecked("bbbbbbbb").display_cached();
+        assert!(a < b);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn checked() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert_eq!(id.display_checked().to_string(), id.to_string());
+            assert_eq!(id.try_display().unwrap().to_string(), id.to_string());
+        }
+        assert_eq!(TinyId::null().display_checked().to_string(), "????????");
+        assert_eq!(
+            TinyId::null().try_display(),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        let id = TinyId::from_bytes_unchecked(*b"\0a\nb\x7fc d");
+        assert_eq!(id.display_checked().to_string(), "?a?b?c?d");
+        assert_eq!(id.try_display(), Err(TinyIdError::InvalidCharacters));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn null_as() {
+        for null in ["(null)", "--------", "", "<none>"] {
+            assert_eq!(TinyId::null().display_null_as(null).to_string(), null);
+            let id = TinyId::random();
+            assert_eq!(id.display_null_as(null).to_string(), id.to_string());
+        }
+        let id = TinyId::from_bytes_unchecked(*b"abc\0efgh");
+        assert_eq!(id.display_null_as("(null)").to_string(), id.to_string());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn table_layout() {
+        let ids = ["aaaaaaaa", "bbbbbbbb", "cccccccc", "dddddddd"].map(TinyId::from_str_unchecked);
+        assert_eq!(table([], 2), "");
+        assert_eq!(
+            table(ids, 0),
+            "#001: aaaaaaaa\n#002: bbbbbbbb\n#003: cccccccc\n#004: dddddddd\n"
+        );
+        assert_eq!(
+            table(ids, 2),
+            "#001: aaaaaaaa | #002: bbbbbbbb\n#003: cccccccc | #004: dddddddd\n"
+        );
+        assert_eq!(
+            table(ids, 3),
+            "#001: aaaaaaaa | #002: bbbbbbbb | #003: cccccccc\n#004: dddddddd\n"
+        );
+        assert_eq!(table([TinyId::null()], 1), "#001: ????????\n");
+
+        let many = table((0..1000).map(|_| TinyId::random()), 4);
+        assert_eq!(many.lines().count(), 250);
+        assert!(many.starts_with("#0001: "));
+        assert!(many.contains(" | #1000: "));
+    }
+}
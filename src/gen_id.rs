@@ -0,0 +1,246 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{collections::IdHashMap, TinyId, TinyIdError};
+
+/// A [`TinyId`] paired with a generation counter, for systems that reuse released IDs and need
+/// to detect stale references to a previous use of the same ID. See [`GenIdPool`].
+///
+/// Displayed as the ID and generation separated by a dot (`abcd1234.3`), and ordered by ID and
+/// then generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenId {
+    id: TinyId,
+    generation: u32,
+}
+
+impl GenId {
+    /// The character separating the ID and generation in the display form of a [`GenId`].
+    pub const SEPARATOR: char = '.';
+
+    /// Create a new [`GenId`] from its parts.
+    #[must_use]
+    pub fn new(id: TinyId, generation: u32) -> Self {
+        Self { id, generation }
+    }
+
+    /// The [`TinyId`] part of this ID.
+    #[must_use]
+    pub fn id(self) -> TinyId {
+        self.id
+    }
+
+    /// The generation of this ID, which is incremented each time the ID is reused.
+    #[must_use]
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+impl std::fmt::Display for GenId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.id, Self::SEPARATOR, self.generation)
+    }
+}
+
+impl std::str::FromStr for GenId {
+    type Err = TinyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, generation) = s
+            .split_once(Self::SEPARATOR)
+            .ok_or(TinyIdError::InvalidCharacters)?;
+        Ok(Self {
+            id: id.parse()?,
+            generation: generation
+                .parse()
+                .map_err(|err: std::num::ParseIntError| TinyIdError::Conversion(err.to_string()))?,
+        })
+    }
+}
+
+/// The state of one [`TinyId`] in a [`GenIdPool`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Slot {
+    generation: u32,
+    live: bool,
+}
+
+/// Allocates [`GenId`]s, reusing released IDs with a new generation, so that a [`GenId`] kept
+/// after its release can be detected as stale instead of silently referring to whatever now uses
+/// the same ID: the classic generational-index pattern, keyed by [`TinyId`]s.
+///
+/// Released IDs are reused first (oldest release first); otherwise a new random ID is generated.
+/// Generations wrap around after `2^32` reuses of the same ID.
+///
+/// ## Example
+/// ```
+/// use tinyid::GenIdPool;
+///
+/// let mut pool = GenIdPool::new();
+/// let a = pool.allocate();
+/// assert!(pool.is_live(a));
+/// assert!(pool.release(a));
+///
+/// let b = pool.allocate();
+/// assert_eq!(b.id(), a.id());
+/// assert_eq!(b.generation(), a.generation() + 1);
+/// assert!(!pool.is_live(a));
+/// assert!(!pool.release(a));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GenIdPool {
+    slots: IdHashMap<Slot>,
+    free: std::collections::VecDeque<TinyId>,
+    live: usize,
+}
+
+impl GenIdPool {
+    /// Create a new, empty [`GenIdPool`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate a [`GenId`], reusing a released ID if there is one.
+    pub fn allocate(&mut self) -> GenId {
+        self.live += 1;
+        let reused = self
+            .free
+            .pop_front()
+            .and_then(|id| Some((id, self.slots.get_mut(&id)?)));
+        if let Some((id, slot)) = reused {
+            slot.live = true;
+            return GenId::new(id, slot.generation);
+        }
+        loop {
+            let id = TinyId::random();
+            if let std::collections::hash_map::Entry::Vacant(entry) = self.slots.entry(id) {
+                entry.insert(Slot {
+                    generation: 0,
+                    live: true,
+                });
+                return GenId::new(id, 0);
+            }
+        }
+    }
+
+    /// Release `gen_id` so its ID can be reused with the next generation. Returns `false` (and
+    /// does nothing) if `gen_id` is stale or was not allocated by this pool.
+    pub fn release(&mut self, gen_id: GenId) -> bool {
+        match self.slots.get_mut(&gen_id.id) {
+            Some(slot) if slot.live && slot.generation == gen_id.generation => {
+                slot.live = false;
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push_back(gen_id.id);
+                self.live -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks whether `gen_id` is currently allocated, i.e. it was allocated by this pool and has
+    /// not been released since.
+    #[must_use]
+    pub fn is_live(&self, gen_id: GenId) -> bool {
+        self.slots
+            .get(&gen_id.id)
+            .is_some_and(|slot| slot.live && slot.generation == gen_id.generation)
+    }
+
+    /// Get the live [`GenId`] currently using `id`, or `None` if `id` is not allocated.
+    #[must_use]
+    pub fn current(&self, id: TinyId) -> Option<GenId> {
+        self.slots
+            .get(&id)
+            .filter(|slot| slot.live)
+            .map(|slot| GenId::new(id, slot.generation))
+    }
+
+    /// The number of live [`GenId`]s.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    /// Checks whether there are no live [`GenId`]s.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    /// The number of released IDs waiting to be reused.
+    #[must_use]
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn display_and_parse() {
+        let id = TinyId::from_str_unchecked("abcd1234");
+        let gen_id = GenId::new(id, 3);
+        assert_eq!(gen_id.to_string(), "abcd1234.3");
+        assert_eq!("abcd1234.3".parse::<GenId>(), Ok(gen_id));
+        assert_eq!(gen_id.id(), id);
+        assert_eq!(gen_id.generation(), 3);
+        assert!(GenId::new(id, 2) < gen_id);
+
+        for bad in [
+            "",
+            "abcd1234",
+            "abcd1234.",
+            "abcd1234.-1",
+            "abcd123!.3",
+            "abcd1234.x",
+        ] {
+            assert!(bad.parse::<GenId>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn pool() {
+        let mut pool = GenIdPool::new();
+        assert!(pool.is_empty());
+        let ids = (0..100).map(|_| pool.allocate()).collect::<Vec<_>>();
+        assert_eq!(pool.len(), 100);
+        assert!(ids
+            .iter()
+            .all(|&id| pool.is_live(id) && id.generation() == 0));
+        assert_eq!(pool.current(ids[0].id()), Some(ids[0]));
+
+        assert!(pool.release(ids[5]));
+        assert!(pool.release(ids[7]));
+        assert!(!pool.release(ids[5]));
+        assert_eq!(pool.len(), 98);
+        assert_eq!(pool.free_count(), 2);
+        assert_eq!(pool.current(ids[5].id()), None);
+
+        let reused = pool.allocate();
+        assert_eq!(reused, GenId::new(ids[5].id(), 1));
+        assert_eq!(pool.allocate(), GenId::new(ids[7].id(), 1));
+        assert!(!pool.is_live(ids[5]));
+        assert!(pool.is_live(reused));
+        assert!(!pool.release(ids[5]));
+        assert_eq!(pool.free_count(), 0);
+
+        let fresh = pool.allocate();
+        assert_eq!(fresh.generation(), 0);
+        assert!(ids.iter().all(|id| id.id() != fresh.id()));
+        assert_eq!(pool.len(), 101);
+
+        assert!(!pool.is_live(GenId::default()));
+        assert!(!pool.release(GenId::new(TinyId::random(), 0)));
+    }
+}
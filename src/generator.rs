@@ -0,0 +1,333 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{IdProvider, TinyId, TinyIdError};
+
+/// The number of candidates [`Generator::try_next_id`] tries before giving up.
+const MAX_ATTEMPTS: usize = 1_000;
+
+/// A small list of common English profanity, see [`Generator::with_default_blocklist`].
+const DEFAULT_BLOCKLIST: &[&str] = &[
+    "anal", "anus", "arse", "bitch", "boob", "butt", "cock", "cum", "cunt", "dick", "dildo", "fag",
+    "fuck", "jizz", "kkk", "nazi", "nigg", "penis", "piss", "poop", "porn", "pussy", "rape", "sex",
+    "shit", "slut", "tits", "twat", "vagina", "wank", "whore",
+];
+
+/// A configurable random [`TinyId`] generator.
+///
+/// ## Blocklist
+/// Human-visible random strings occasionally spell embarrassing words. Words added with
+/// [`Generator::block`] (or the built-in list from [`Generator::with_default_blocklist`]) are
+/// matched case-insensitively anywhere in a candidate ID, and matching candidates are silently
+/// regenerated.
+///
+/// ## Leading Letter
+/// With [`Generator::leading_letter`], every generated ID starts with an ASCII letter (see
+/// [`TinyId::is_identifier_safe`]), so it can be used directly as an HTML element id or CSS class
+/// name.
+///
+/// ## Example
+/// ```
+/// use tinyid::{Generator, IdProvider};
+///
+/// let mut gen = Generator::new().with_default_blocklist().block("acme");
+/// let id = gen.next_id();
+/// assert!(!gen.is_blocked(id));
+/// assert!(gen.is_blocked("xxACMExx".parse().unwrap()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Generator {
+    blocklist: Vec<Vec<u8>>,
+    leading_letter: bool,
+}
+
+impl Generator {
+    /// Create a new [`Generator`] with no options set, which generates the same IDs as
+    /// [`TinyId::random`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject generated IDs containing `word`, ignoring ASCII case. Empty words, and words longer
+    /// than a [`TinyId`], are ignored as they could never (or would always) match.
+    #[must_use]
+    pub fn block(mut self, word: &str) -> Self {
+        if (1..=8).contains(&word.len()) {
+            let word = word.to_ascii_lowercase().into_bytes();
+            if !self.blocklist.contains(&word) {
+                self.blocklist.push(word);
+            }
+        }
+        self
+    }
+
+    /// Reject generated IDs containing any of `words`, see [`Generator::block`].
+    #[must_use]
+    pub fn block_all<S: AsRef<str>>(self, words: impl IntoIterator<Item = S>) -> Self {
+        words
+            .into_iter()
+            .fold(self, |gen, word| gen.block(word.as_ref()))
+    }
+
+    /// Reject generated IDs containing common English profanity, in addition to any other blocked
+    /// words. The list is intentionally short; add application specific words with
+    /// [`Generator::block`].
+    #[must_use]
+    pub fn with_default_blocklist(self) -> Self {
+        self.block_all(DEFAULT_BLOCKLIST)
+    }
+
+    /// Only generate IDs whose first character is an ASCII letter, see
+    /// [`TinyId::is_identifier_safe`].
+    #[must_use]
+    pub fn leading_letter(mut self) -> Self {
+        self.leading_letter = true;
+        self
+    }
+
+    /// Checks whether this generator only generates IDs starting with a letter.
+    #[must_use]
+    pub fn requires_leading_letter(&self) -> bool {
+        self.leading_letter
+    }
+
+    /// The blocked words, lowercased.
+    pub fn blocklist(&self) -> impl Iterator<Item = &str> + '_ {
+        self.blocklist
+            .iter()
+            .map(|word| std::str::from_utf8(word).unwrap_or_default())
+    }
+
+    /// Checks whether `id` contains any blocked word, ignoring ASCII case.
+    #[must_use]
+    pub fn is_blocked(&self, id: TinyId) -> bool {
+        let lower = id.to_bytes().to_ascii_lowercase();
+        self.blocklist.iter().any(|word| {
+            lower
+                .windows(word.len())
+                .any(|window| window == word.as_slice())
+        })
+    }
+
+    /// Checks whether `id` satisfies this generator's options, i.e. whether this generator could
+    /// have generated it.
+    #[must_use]
+    pub fn accepts(&self, id: TinyId) -> bool {
+        (!self.leading_letter || id.is_identifier_safe()) && !self.is_blocked(id)
+    }
+
+    /// Generate a new random [`TinyId`] satisfying this generator's options.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if no acceptable ID was found after many attempts,
+    ///   which only happens if the options reject (nearly) every ID.
+    pub fn try_next_id(&self) -> Result<TinyId, TinyIdError> {
+        TinyId::random_where(|id| self.accepts(id))
+    }
+
+    /// Generate a new random [`TinyId`] satisfying this generator's options.
+    ///
+    /// ## Panics
+    /// If the options reject (nearly) every ID, see [`Generator::try_next_id`].
+    #[must_use]
+    pub fn next_id(&self) -> TinyId {
+        self.try_next_id()
+            .expect("Generator options reject (nearly) every ID")
+    }
+}
+
+impl IdProvider for Generator {
+    fn next_id(&mut self) -> TinyId {
+        Generator::next_id(self)
+    }
+}
+
+impl TinyId {
+    /// Checks whether this [`TinyId`] is valid and starts with an ASCII letter, so that it is a
+    /// valid HTML element id and CSS class name, and (in languages which allow `-`, or after
+    /// replacing it) a valid identifier. See [`Generator::leading_letter`].
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// assert!(TinyId::from_str_unchecked("a1b2c3d4").is_identifier_safe());
+    /// assert!(!TinyId::from_str_unchecked("1a2b3c4d").is_identifier_safe());
+    /// assert!(!TinyId::from_str_unchecked("_abcdefg").is_identifier_safe());
+    /// assert!(!TinyId::null().is_identifier_safe());
+    /// ```
+    #[must_use]
+    pub fn is_identifier_safe(self) -> bool {
+        self.is_valid() && self.data[0].is_ascii_alphabetic()
+    }
+
+    /// The number of candidates [`TinyId::random_where`] tries before giving up.
+    pub const RANDOM_WHERE_ATTEMPTS: usize = MAX_ATTEMPTS;
+
+    /// Generate a random [`TinyId`] for which `predicate` returns `true`, regenerating up to
+    /// [`TinyId::RANDOM_WHERE_ATTEMPTS`] times. See [`TinyId::random_where_within`] to choose the
+    /// number of attempts, for predicates that accept only a small fraction of IDs.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if no candidate was accepted.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random_where(|id| id.to_bytes()[0].is_ascii_alphabetic()).unwrap();
+    /// assert!(id.to_bytes()[0].is_ascii_alphabetic());
+    /// ```
+    pub fn random_where(predicate: impl FnMut(TinyId) -> bool) -> Result<Self, TinyIdError> {
+        Self::random_where_within(Self::RANDOM_WHERE_ATTEMPTS, predicate)
+    }
+
+    /// Generate a random [`TinyId`] that is not already in `ids`, insert it, and return it. This
+    /// replaces the common "generate, check, insert" loop.
+    ///
+    /// This retries until an unused ID is found. With `2^48` possible IDs that is effectively
+    /// always immediate, but see [`UniqueGenerator`](crate::UniqueGenerator) for a bounded,
+    /// thread-safe alternative.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::collections::HashSet;
+    /// use tinyid::TinyId;
+    ///
+    /// let mut ids = HashSet::new();
+    /// let id = TinyId::random_unique(&mut ids);
+    /// assert!(ids.contains(&id));
+    /// ```
+    pub fn random_unique<S: std::hash::BuildHasher>(
+        ids: &mut std::collections::HashSet<TinyId, S>,
+    ) -> Self {
+        loop {
+            let id = Self::random();
+            if ids.insert(id) {
+                return id;
+            }
+        }
+    }
+
+    /// Generate a random [`TinyId`] for which `predicate` returns `true`, trying at most
+    /// `attempts` candidates.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if no candidate was accepted.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// // Roughly 1 in 4096 IDs start with "zz".
+    /// let id = TinyId::random_where_within(1_000_000, |id| id.starts_with("zz")).unwrap();
+    /// assert!(id.starts_with("zz"));
+    /// ```
+    pub fn random_where_within(
+        attempts: usize,
+        mut predicate: impl FnMut(TinyId) -> bool,
+    ) -> Result<Self, TinyIdError> {
+        (0..attempts)
+            .map(|_| Self::random())
+            .find(|&id| predicate(id))
+            .ok_or(TinyIdError::GenerationFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn blocklist() {
+        let gen = Generator::new()
+            .block("Ab")
+            .block("ab")
+            .block("")
+            .block("123456789");
+        assert_eq!(gen.blocklist().collect::<Vec<_>>(), ["ab"]);
+        assert!(gen.is_blocked(TinyId::from_str_unchecked("xxxxxxAB")));
+        assert!(gen.is_blocked(TinyId::from_str_unchecked("aBxxxxxx")));
+        assert!(!gen.is_blocked(TinyId::from_str_unchecked("axbxxxxx")));
+        assert!(!Generator::new().is_blocked(TinyId::from_str_unchecked("fuckfuck")));
+
+        let gen = Generator::new().with_default_blocklist();
+        assert!(gen.is_blocked(TinyId::from_str_unchecked("xxFuCkxx")));
+        assert!(gen.blocklist().count() >= DEFAULT_BLOCKLIST.len());
+
+        let gen = Generator::new().block_all(["a", "b", "c"]);
+        for _ in 0..1000 {
+            let id = gen.next_id();
+            assert!(id.is_valid());
+            assert!(!id.to_bytes().iter().any(|b| b"aAbBcC".contains(b)));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn leading_letter() {
+        assert!(!Generator::new().requires_leading_letter());
+        let gen = Generator::new().leading_letter().block("x");
+        assert!(gen.requires_leading_letter());
+        for _ in 0..1000 {
+            let id = gen.next_id();
+            assert!(id.is_identifier_safe());
+            assert!(gen.accepts(id));
+        }
+        assert!(!gen.accepts(TinyId::from_str_unchecked("-abcdefg")));
+        assert!(!gen.accepts(TinyId::from_str_unchecked("9abcdefg")));
+        assert!(!gen.accepts(TinyId::from_str_unchecked("abcdefgx")));
+        assert!(Generator::new().accepts(TinyId::from_str_unchecked("9abcdefg")));
+        assert!(TinyId::from_str_unchecked("Z-------").is_identifier_safe());
+        assert!(!TinyId::from_bytes_unchecked(*b"ab\0defgh").is_identifier_safe());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn rejects_everything() {
+        let everything = TinyId::LETTERS
+            .iter()
+            .map(|&b| (b as char).to_string())
+            .collect::<Vec<_>>();
+        let gen = Generator::new().block_all(&everything);
+        assert_eq!(gen.try_next_id(), Err(TinyIdError::GenerationFailure));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random_where() {
+        for _ in 0..100 {
+            let id = TinyId::random_where(|id| id.to_u64() % 4 == 3).unwrap();
+            assert_eq!(id.to_u64() % 4, 3);
+        }
+        assert_eq!(
+            TinyId::random_where(|_| false),
+            Err(TinyIdError::GenerationFailure)
+        );
+
+        let mut calls = 0;
+        let result = TinyId::random_where_within(10, |_| {
+            calls += 1;
+            false
+        });
+        assert_eq!(result, Err(TinyIdError::GenerationFailure));
+        assert_eq!(calls, 10);
+        assert!(TinyId::random_where_within(0, |_| true).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random_unique() {
+        let mut ids = std::collections::HashSet::new();
+        let generated = (0..10_000)
+            .map(|_| TinyId::random_unique(&mut ids))
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 10_000);
+        assert!(generated.iter().all(|id| id.is_valid() && ids.contains(id)));
+    }
+}
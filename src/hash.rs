@@ -0,0 +1,48 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Small, stable (non-cryptographic) hash functions. Unlike [`std::hash::Hasher`]
+//! implementations, their output is guaranteed never to change between releases, so they are
+//! safe to use for anything that must be reproducible across processes and versions.
+
+/// The increment used by the splitmix64 generator, see [`mix64`].
+pub(crate) const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// The splitmix64 output function, a fast bijective mixer for 64-bit values.
+#[must_use]
+pub(crate) const fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Hash arbitrary bytes to 64 bits using FNV-1a, finished with [`mix64`] so that every output
+/// bit depends on every input byte.
+#[must_use]
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01B3;
+    mix64(
+        bytes
+            .iter()
+            .fold(OFFSET, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(PRIME)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn stable() {
+        // These values must never change, IDs derived from them are expected to be reproducible.
+        assert_eq!(mix64(0), 0);
+        assert_eq!(mix64(1), 0x5692_161D_100B_05E5);
+        assert_eq!(hash_bytes(b""), mix64(0xCBF2_9CE4_8422_2325));
+        assert_ne!(hash_bytes(b"a"), hash_bytes(b"b"));
+    }
+}
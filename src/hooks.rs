@@ -0,0 +1,49 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::TinyId;
+
+/// A callback observing one [`TinyId`].
+type Hook = Box<dyn Fn(TinyId) + Send + Sync>;
+
+/// The callbacks registered with a generator's `on_generate` and `on_collision`, which are called
+/// in the order they were registered.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    generated: Vec<Hook>,
+    collided: Vec<Hook>,
+}
+
+impl Hooks {
+    /// Register a callback for each issued ID.
+    pub(crate) fn on_generate(&mut self, hook: impl Fn(TinyId) + Send + Sync + 'static) {
+        self.generated.push(Box::new(hook));
+    }
+
+    /// Register a callback for each candidate rejected because it was already issued.
+    pub(crate) fn on_collision(&mut self, hook: impl Fn(TinyId) + Send + Sync + 'static) {
+        self.collided.push(Box::new(hook));
+    }
+
+    /// Call the generate callbacks with an issued ID.
+    pub(crate) fn generated(&self, id: TinyId) {
+        self.generated.iter().for_each(|hook| hook(id));
+    }
+
+    /// Call the collision callbacks with a rejected candidate.
+    pub(crate) fn collided(&self, id: TinyId) {
+        self.collided.iter().for_each(|hook| hook(id));
+    }
+}
+
+impl std::fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hooks")
+            .field("generated", &self.generated.len())
+            .field("collided", &self.collided.len())
+            .finish()
+    }
+}
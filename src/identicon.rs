@@ -0,0 +1,134 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Write;
+
+use crate::{hash::hash_bytes, TinyId};
+
+/// A small, symmetric pixel-art image derived from a [`TinyId`], giving each ID a recognizable
+/// visual fingerprint. The same ID always produces the same identicon.
+///
+/// The image is a [`Identicon::SIZE`] x [`Identicon::SIZE`] grid of cells, mirrored left to right,
+/// filled with a single color. Render it with [`Identicon::to_svg`], or draw
+/// [`Identicon::cells`] yourself.
+///
+/// ## Example
+/// ```
+/// use tinyid::{Identicon, TinyId};
+///
+/// let id = TinyId::from_str_unchecked("abcdefgh");
+/// let icon = Identicon::new(id);
+/// assert_eq!(icon, Identicon::new(id));
+/// let svg = icon.to_svg(64);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Identicon {
+    cells: [[bool; Identicon::SIZE]; Identicon::SIZE],
+    color: (u8, u8, u8),
+}
+
+impl Identicon {
+    /// The number of cells along each side of an identicon.
+    pub const SIZE: usize = 5;
+
+    /// Create the identicon for `id`.
+    #[must_use]
+    pub fn new(id: TinyId) -> Self {
+        let hash = hash_bytes(&id.to_bytes());
+        let mut cells = [[false; Self::SIZE]; Self::SIZE];
+        let half = Self::SIZE.div_ceil(2);
+        for (y, row) in cells.iter_mut().enumerate() {
+            for x in 0..half {
+                let filled = hash >> (y * half + x) & 1 == 1;
+                row[x] = filled;
+                row[Self::SIZE - 1 - x] = filled;
+            }
+        }
+        Self {
+            cells,
+            color: id.to_color(),
+        }
+    }
+
+    /// The grid of cells, indexed by row and then column. `true` cells are filled with
+    /// [`Identicon::color`].
+    #[must_use]
+    pub fn cells(&self) -> [[bool; Identicon::SIZE]; Identicon::SIZE] {
+        self.cells
+    }
+
+    /// The RGB color of the filled cells, the same as [`TinyId::to_color`].
+    #[must_use]
+    pub fn color(&self) -> (u8, u8, u8) {
+        self.color
+    }
+
+    /// Render this identicon as an SVG image `size` pixels wide and tall, on a transparent
+    /// background.
+    #[must_use]
+    pub fn to_svg(&self, size: u32) -> String {
+        let (r, g, b) = self.color;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+             viewBox=\"0 0 {n} {n}\" shape-rendering=\"crispEdges\">\
+             <g fill=\"#{r:02x}{g:02x}{b:02x}\">",
+            n = Self::SIZE,
+        );
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &filled)| filled) {
+                // Writing to a `String` can not fail.
+                let _ = write!(svg, "<rect x=\"{x}\" y=\"{y}\" width=\"1\" height=\"1\"/>");
+            }
+        }
+        svg.push_str("</g></svg>");
+        svg
+    }
+}
+
+impl TinyId {
+    /// Get the [`Identicon`] for this ID.
+    #[must_use]
+    pub fn identicon(&self) -> Identicon {
+        Identicon::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn deterministic_and_symmetric() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            let icon = id.identicon();
+            assert_eq!(icon, Identicon::new(id));
+            for row in icon.cells() {
+                for x in 0..Identicon::SIZE {
+                    assert_eq!(row[x], row[Identicon::SIZE - 1 - x]);
+                }
+            }
+        }
+        let a = TinyId::from_str_unchecked("aaaaaaaa").identicon();
+        let b = TinyId::from_str_unchecked("aaaaaaab").identicon();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn svg() {
+        let icon = TinyId::from_str_unchecked("abcdefgh").identicon();
+        let svg = icon.to_svg(100);
+        let filled = icon.cells().iter().flatten().filter(|&&c| c).count();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\""));
+        assert!(svg.ends_with("</g></svg>"));
+        assert_eq!(svg.matches("<rect").count(), filled);
+        let (r, g, b) = icon.color();
+        assert!(svg.contains(&format!("fill=\"#{r:02x}{g:02x}{b:02x}\"")));
+    }
+}
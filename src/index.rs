@@ -0,0 +1,627 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! The bijection between valid [`TinyId`]s and the integers `0..64^8`.
+//!
+//! Each letter is worth 6 bits, ranked by its byte value (see [`ORDERED_LETTERS`]), with the
+//! first letter being the most significant. Because the ranks preserve byte order, the index
+//! order of two valid IDs is always the same as their [`Ord`] order.
+
+use std::ops::Bound;
+
+use crate::{Block, TinyId, TinyIdError};
+
+/// The number of distinct valid [`TinyId`]s, `64^8` (aka `2^48`).
+pub(crate) const KEYSPACE: u64 = 1 << 48;
+
+/// [`TinyId::LETTERS`] sorted by byte value, so a letter's position in this array (its rank)
+/// preserves the ordering of the letters themselves.
+pub(crate) const ORDERED_LETTERS: [u8; TinyId::LETTER_COUNT] =
+    *b"-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+/// Get the rank of the given byte within [`ORDERED_LETTERS`], or `None` if it is not a valid
+/// letter.
+pub(crate) const fn letter_rank(byte: u8) -> Option<u8> {
+    match byte {
+        b'-' => Some(0),
+        b'0'..=b'9' => Some(byte - b'0' + 1),
+        b'A'..=b'Z' => Some(byte - b'A' + 11),
+        b'_' => Some(37),
+        b'a'..=b'z' => Some(byte - b'a' + 38),
+        _ => None,
+    }
+}
+
+impl TinyId {
+    /// The number of bits that can be packed into a [`TinyId`] with [`TinyId::encode_payload`].
+    pub const PAYLOAD_BITS: u32 = 48;
+
+    /// Pack a payload of up to 48 bits into a valid [`TinyId`], so small integers (offsets,
+    /// compact foreign keys, etc.) can travel through systems which only accept IDs. Every
+    /// payload maps to a different ID, and [`TinyId::decode_payload`] recovers it.
+    ///
+    /// Returns `None` if `payload` does not fit in [`TinyId::PAYLOAD_BITS`] bits.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::encode_payload(1234).unwrap();
+    /// assert!(id.is_valid());
+    /// assert_eq!(id.to_string(), "------IH");
+    /// assert_eq!(id.decode_payload(), Some(1234));
+    /// assert_eq!(TinyId::encode_payload(1 << 48), None);
+    /// ```
+    #[must_use]
+    pub fn encode_payload(payload: u64) -> Option<Self> {
+        Self::from_index(payload)
+    }
+
+    /// Extract the payload packed by [`TinyId::encode_payload`], or `None` if this ID is not
+    /// valid. Every valid ID decodes to some payload less than `2^48`.
+    #[must_use]
+    pub fn decode_payload(self) -> Option<u64> {
+        self.to_index()
+    }
+
+    /// Convert this [`TinyId`] to a number that survives a round trip through JavaScript (or any
+    /// other JSON consumer that parses numbers as IEEE-754 doubles), or `None` if it is not
+    /// valid. This is the ID's position in the keyspace (see [`TinyId::to_index`]), which is
+    /// always below `2^48` and so well within `Number.MAX_SAFE_INTEGER` (`2^53 - 1`).
+    ///
+    /// Use this rather than [`TinyId::to_u64`] for numeric IDs sent to JSON consumers: every
+    /// valid ID's `to_u64` is above `2^53`, so it is silently rounded to a different number.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let n = id.to_js_safe_number().unwrap();
+    /// assert!(n < 1 << 53);
+    /// assert_eq!(TinyId::from_js_safe_number(n), Some(id));
+    /// assert!(id.to_u64() > 1 << 53);
+    /// ```
+    #[must_use]
+    pub fn to_js_safe_number(self) -> Option<u64> {
+        self.to_index()
+    }
+
+    /// Create a [`TinyId`] from a number given by [`TinyId::to_js_safe_number`], or `None` if
+    /// `n` is not less than `2^48`.
+    #[must_use]
+    pub fn from_js_safe_number(n: u64) -> Option<Self> {
+        Self::from_index(n)
+    }
+
+    /// Create a [`TinyId`] directly from 48 bits of caller-provided entropy, without touching any
+    /// random number generator. For systems with their own randomness pipelines (HSMs, DRBGs,
+    /// etc.). Every input maps to a different ID, so uniformly random input gives uniformly
+    /// random IDs.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_entropy(&[0x9c, 0x41, 0x07, 0xee, 0x5a, 0x13]);
+    /// assert!(id.is_valid());
+    /// assert_eq!(id, TinyId::from_entropy(&[0x9c, 0x41, 0x07, 0xee, 0x5a, 0x13]));
+    /// ```
+    #[must_use]
+    pub fn from_entropy(bytes: &[u8; 6]) -> Self {
+        let mut padded = [0u8; 8];
+        padded[2..].copy_from_slice(bytes);
+        Self::from_index_wrapping(u64::from_be_bytes(padded))
+    }
+
+    /// Advance this [`TinyId`] by `n` positions in keyspace order, wrapping from `zzzzzzzz` back
+    /// around to `--------`. Useful for jump-ahead schemes and striped allocation. Invalid IDs
+    /// (including the null ID) have no position, so they give the null ID.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("-------z");
+    /// assert_eq!(id.wrapping_add(1), "------0-");
+    /// assert_eq!(TinyId::from_str_unchecked("zzzzzzzz").wrapping_add(1), "--------");
+    /// assert!(TinyId::null().wrapping_add(1).is_null());
+    /// ```
+    #[must_use]
+    pub fn wrapping_add(self, n: u64) -> Self {
+        self.to_index().map_or_else(Self::null, |index| {
+            Self::from_index_wrapping(index.wrapping_add(n % KEYSPACE))
+        })
+    }
+
+    /// Advance this [`TinyId`] by `n` positions in keyspace order, or `None` if that would go past
+    /// `zzzzzzzz` or this ID is not valid.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("--------");
+    /// assert_eq!(id.checked_add(64), Some(TinyId::from_str_unchecked("------0-")));
+    /// assert_eq!(TinyId::from_str_unchecked("zzzzzzzz").checked_add(1), None);
+    /// ```
+    #[must_use]
+    pub fn checked_add(self, n: u64) -> Option<Self> {
+        Self::from_index(self.to_index()?.checked_add(n)?)
+    }
+
+    /// Get how many positions apart this [`TinyId`] and `other` are in keyspace order, regardless
+    /// of which comes first, e.g. for partition balancing diagnostics and range sizing. Returns
+    /// `None` if either ID is not valid, as invalid IDs have no position.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let a = TinyId::from_str_unchecked("--------");
+    /// let b = TinyId::from_str_unchecked("------0-");
+    /// assert_eq!(a.distance_to(&b), Some(64));
+    /// assert_eq!(b.distance_to(&a), Some(64));
+    /// assert_eq!(a.distance_to(&TinyId::null()), None);
+    /// ```
+    #[must_use]
+    pub fn distance_to(&self, other: &TinyId) -> Option<u64> {
+        Some(self.to_index()?.abs_diff(other.to_index()?))
+    }
+
+    /// Get the [`TinyId`] halfway between `a` and `b` in keyspace order, which is strictly
+    /// between them in [`Ord`] order as well, for fractional-indexing style ordered lists where a
+    /// new item is keyed between its neighbors. `a` and `b` may be given in either order.
+    ///
+    /// Returns `None` if either ID is not valid, or if there is no ID between them (they are
+    /// equal or adjacent). Each insertion halves the gap, so repeatedly inserting at the same spot
+    /// runs out after at most 48 insertions.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let a = TinyId::from_str_unchecked("aaaaaaaa");
+    /// let b = TinyId::from_str_unchecked("aaaaaaba");
+    /// let mid = TinyId::between(a, b).unwrap();
+    /// assert!(a < mid && mid < b);
+    /// assert_eq!(TinyId::between(a, a.checked_add(1).unwrap()), None);
+    /// ```
+    #[must_use]
+    pub fn between(a: TinyId, b: TinyId) -> Option<TinyId> {
+        let (a, b) = (a.to_index()?, b.to_index()?);
+        let (low, high) = (a.min(b), a.max(b));
+        (high - low >= 2).then(|| Self::from_index_wrapping(low + (high - low) / 2))
+    }
+
+    /// Iterate over every valid [`TinyId`] that starts with `prefix`, in order. Each free letter
+    /// multiplies the count by 64, so this is meant for exhaustively enumerating small
+    /// sub-keyspaces (e.g. 2-3 free letters) for testing or allocation.
+    ///
+    /// The IDs sharing a prefix are a contiguous, aligned range of the keyspace, so they are
+    /// returned as a [`Block`] of `64^(8 - prefix.len())` IDs, numbered by the prefix's position.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `prefix` is longer than 8 bytes.
+    /// - [`TinyIdError::InvalidCharacters`] if `prefix` contains invalid characters.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let ids = TinyId::iter_with_prefix("abcdef").unwrap();
+    /// assert_eq!(ids.len(), 64 * 64);
+    /// let ids: Vec<TinyId> = ids.collect();
+    /// assert_eq!(ids[0], "abcdef--");
+    /// assert_eq!(ids[ids.len() - 1], "abcdefzz");
+    /// assert!(ids.iter().all(|id| id.starts_with("abcdef")));
+    /// ```
+    pub fn iter_with_prefix(prefix: &str) -> Result<Block, TinyIdError> {
+        let prefix = prefix.as_bytes();
+        if prefix.len() > 8 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        let number = prefix
+            .iter()
+            .try_fold(0u64, |acc, &b| {
+                letter_rank(b).map(|rank| acc * 64 + u64::from(rank))
+            })
+            .ok_or(TinyIdError::InvalidCharacters)?;
+        Ok(Block::new(number, 1 << (6 * (8 - prefix.len()))))
+    }
+
+    /// Get the bounds of every [`TinyId`] that starts with `prefix`, for use with
+    /// [`BTreeMap::range`](std::collections::BTreeMap::range) and
+    /// [`BTreeSet::range`](std::collections::BTreeSet::range), so ordered indexes can find all IDs
+    /// with a prefix without scanning.
+    ///
+    /// The bounds compare bytes, so they also capture IDs whose remaining bytes are not valid, and
+    /// the bounds themselves are generally not valid IDs. An empty prefix is unbounded on both
+    /// sides.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `prefix` is longer than 8 bytes.
+    /// - [`TinyIdError::InvalidCharacters`] if `prefix` contains invalid characters.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use tinyid::TinyId;
+    ///
+    /// let ids: BTreeSet<TinyId> = ["aaaaaaaa", "abzzzzzz", "ab------", "ac------", "b-------"]
+    ///     .into_iter()
+    ///     .map(TinyId::from_str_unchecked)
+    ///     .collect();
+    /// let found: Vec<String> = ids
+    ///     .range(TinyId::prefix_bounds("ab").unwrap())
+    ///     .map(ToString::to_string)
+    ///     .collect();
+    /// assert_eq!(found, ["ab------", "abzzzzzz"]);
+    /// ```
+    pub fn prefix_bounds(prefix: &str) -> Result<(Bound<TinyId>, Bound<TinyId>), TinyIdError> {
+        let prefix = prefix.as_bytes();
+        if prefix.len() > 8 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        if !prefix.iter().all(|&b| Self::is_valid_byte(b)) {
+            return Err(TinyIdError::InvalidCharacters);
+        }
+        let Some((&last, _)) = prefix.split_last() else {
+            return Ok((Bound::Unbounded, Bound::Unbounded));
+        };
+        let mut start = Self::NULL_DATA;
+        start[..prefix.len()].copy_from_slice(prefix);
+        // Letters are all below `u8::MAX`, so the byte after the last letter can not overflow.
+        let mut end = start;
+        end[prefix.len() - 1] = last + 1;
+        Ok((
+            Bound::Included(Self { data: start }),
+            Bound::Excluded(Self { data: end }),
+        ))
+    }
+
+    /// Create the [`TinyId`] at the given position in the keyspace, the inverse of
+    /// [`TinyId::to_index`]. Returns `None` if `index` is not less than `64^8`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// assert_eq!(TinyId::from_index(id.to_index().unwrap()), Some(id));
+    /// assert_eq!(TinyId::from_index(1 << 48), None);
+    /// ```
+    #[must_use]
+    pub fn from_index(index: u64) -> Option<Self> {
+        (index < KEYSPACE).then(|| Self::from_index_wrapping(index))
+    }
+
+    /// Create the [`TinyId`] at the given position in the keyspace. Only the low 48 bits of
+    /// `index` are used, so indices past the end wrap around.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub(crate) fn from_index_wrapping(index: u64) -> Self {
+        let mut data = Self::NULL_DATA;
+        let mut rest = index % KEYSPACE;
+        for b in data.iter_mut().rev() {
+            *b = ORDERED_LETTERS[(rest % 64) as usize];
+            rest /= 64;
+        }
+        Self { data }
+    }
+
+    /// Get a key whose lexicographic order is the same as the [`Ord`] order of [`TinyId`]s and of
+    /// their [`TinyId::to_u64`] values, for storing IDs in ordered KV stores or spreadsheets.
+    ///
+    /// Since the letters are compared by byte value and stored most significant first, this is
+    /// simply the ID's bytes; the plain display form sorts the same way under byte-wise (e.g.
+    /// `C` locale) string comparison. The null ID sorts before every valid ID.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let a = TinyId::from_str_unchecked("A_______");
+    /// let b = TinyId::from_str_unchecked("a-------");
+    /// assert!(a < b);
+    /// assert!(a.sort_key() < b.sort_key());
+    /// assert!(a.to_u64() < b.to_u64());
+    /// assert_eq!(a.sort_key(), *b"A_______");
+    /// ```
+    #[must_use]
+    pub fn sort_key(self) -> [u8; 8] {
+        self.data
+    }
+
+    /// Get the position of this [`TinyId`] in the keyspace (`0..64^8`), treating it as a base-64
+    /// number whose digits are the letters ranked by byte value. Index order is the same as
+    /// [`Ord`] order, making this a foundation for counters, permutations and dense packing.
+    /// Returns `None` if this ID is not valid.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// assert_eq!(TinyId::from_str_unchecked("--------").to_index(), Some(0));
+    /// assert_eq!(TinyId::from_str_unchecked("------0-").to_index(), Some(64));
+    /// assert_eq!(TinyId::from_str_unchecked("zzzzzzzz").to_index(), Some((1 << 48) - 1));
+    /// assert_eq!(TinyId::null().to_index(), None);
+    /// ```
+    #[must_use]
+    pub fn to_index(self) -> Option<u64> {
+        self.data.iter().try_fold(0u64, |acc, &b| {
+            letter_rank(b).map(|rank| acc * 64 + u64::from(rank))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn ordered_letters() {
+        for (rank, &letter) in (0u8..).zip(ORDERED_LETTERS.iter()) {
+            assert!(TinyId::LETTERS.contains(&letter));
+            assert_eq!(letter_rank(letter), Some(rank));
+        }
+        assert!(ORDERED_LETTERS.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(letter_rank(TinyId::NULL_CHAR), None);
+        assert_eq!(letter_rank(b'!'), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn index_roundtrip() {
+        assert_eq!(TinyId::from_index_wrapping(0).to_string(), "--------");
+        assert_eq!(
+            TinyId::from_index_wrapping(KEYSPACE - 1).to_string(),
+            "zzzzzzzz"
+        );
+        assert_eq!(
+            TinyId::from_index_wrapping(KEYSPACE),
+            TinyId::from_index_wrapping(0)
+        );
+        assert_eq!(TinyId::from_index_wrapping(65).to_string(), "------00");
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            let index = id.to_index().unwrap();
+            assert!(index < KEYSPACE);
+            assert_eq!(TinyId::from_index_wrapping(index), id);
+        }
+        assert_eq!(TinyId::null().to_index(), None);
+        assert_eq!(
+            TinyId::from_index(KEYSPACE - 1),
+            Some(TinyId::from_index_wrapping(KEYSPACE - 1))
+        );
+        assert_eq!(TinyId::from_index(KEYSPACE), None);
+        assert_eq!(TinyId::from_index(u64::MAX), None);
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            #[allow(
+                clippy::cast_precision_loss,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss
+            )]
+            let through_f64 = id.to_js_safe_number().unwrap() as f64 as u64;
+            assert_eq!(TinyId::from_js_safe_number(through_f64), Some(id));
+        }
+        assert_eq!(TinyId::null().to_js_safe_number(), None);
+        assert_eq!(TinyId::from_js_safe_number(KEYSPACE), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn payload() {
+        assert_eq!(KEYSPACE, 1 << TinyId::PAYLOAD_BITS);
+        for payload in [0, 1, 63, 64, 1_000_000, KEYSPACE - 1] {
+            let id = TinyId::encode_payload(payload).unwrap();
+            assert!(id.is_valid());
+            assert_eq!(id.decode_payload(), Some(payload));
+        }
+        assert_eq!(TinyId::encode_payload(KEYSPACE), None);
+        assert_eq!(TinyId::encode_payload(u64::MAX), None);
+        assert_eq!(TinyId::null().decode_payload(), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn index_order_matches_ord() {
+        for _ in 0..1000 {
+            let a = TinyId::random();
+            let b = TinyId::random();
+            assert_eq!(a.cmp(&b), a.to_index().cmp(&b.to_index()));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn sort_key_order() {
+        let mut ids = (0..1000).map(|_| TinyId::random()).collect::<Vec<_>>();
+        ids.push(TinyId::null());
+        let mut by_ord = ids.clone();
+        by_ord.sort();
+        let mut by_key = ids.clone();
+        by_key.sort_by_key(|id| id.sort_key());
+        let mut by_u64 = ids.clone();
+        by_u64.sort_by_key(|id| id.to_u64());
+        let mut by_string = ids.clone();
+        by_string.sort_by_key(ToString::to_string);
+        assert_eq!(by_ord, by_key);
+        assert_eq!(by_ord, by_u64);
+        assert_eq!(by_ord, by_string);
+        assert_eq!(by_ord[0], TinyId::null());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn from_entropy() {
+        assert_eq!(TinyId::from_entropy(&[0; 6]).to_string(), "--------");
+        assert_eq!(TinyId::from_entropy(&[0xFF; 6]).to_string(), "zzzzzzzz");
+        assert_eq!(
+            TinyId::from_entropy(&[0, 0, 0, 0, 0x04, 0xD2]),
+            TinyId::encode_payload(1234).unwrap()
+        );
+        for _ in 0..1000 {
+            let bytes: [u8; 6] = std::array::from_fn(|_| fastrand::u8(..));
+            let id = TinyId::from_entropy(&bytes);
+            assert!(id.is_valid());
+            assert_eq!(id.decode_payload().unwrap().to_be_bytes()[2..], bytes);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn add() {
+        let first = TinyId::from_index_wrapping(0);
+        let last = TinyId::from_index_wrapping(KEYSPACE - 1);
+        assert_eq!(first.wrapping_add(0), first);
+        assert_eq!(first.wrapping_add(1).to_string(), "-------0");
+        assert_eq!(last.wrapping_add(1), first);
+        assert_eq!(last.wrapping_add(KEYSPACE), last);
+        assert_eq!(
+            first.wrapping_add(u64::MAX),
+            TinyId::from_index_wrapping(u64::MAX)
+        );
+        assert_eq!(first.checked_add(KEYSPACE - 1), Some(last));
+        assert_eq!(first.checked_add(KEYSPACE), None);
+        assert_eq!(last.checked_add(1), None);
+        assert_eq!(last.checked_add(u64::MAX), None);
+        assert!(TinyId::null().wrapping_add(5).is_null());
+        assert_eq!(TinyId::null().checked_add(5), None);
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            let n = fastrand::u64(..KEYSPACE);
+            let index = id.to_index().unwrap();
+            assert_eq!(id.wrapping_add(n).to_index(), Some((index + n) % KEYSPACE));
+            assert_eq!(
+                id.checked_add(n),
+                (index + n < KEYSPACE).then(|| id.wrapping_add(n))
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn distance() {
+        let first = TinyId::from_index_wrapping(0);
+        let last = TinyId::from_index_wrapping(KEYSPACE - 1);
+        assert_eq!(first.distance_to(&first), Some(0));
+        assert_eq!(first.distance_to(&last), Some(KEYSPACE - 1));
+        assert_eq!(last.distance_to(&first), Some(KEYSPACE - 1));
+        assert_eq!(TinyId::null().distance_to(&first), None);
+        assert_eq!(first.distance_to(&TinyId::null()), None);
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            let n = fastrand::u64(..1_000_000);
+            if let Some(later) = id.checked_add(n) {
+                assert_eq!(id.distance_to(&later), Some(n));
+                assert_eq!(later.distance_to(&id), Some(n));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn iter_with_prefix() {
+        let ids: Vec<TinyId> = TinyId::iter_with_prefix("abcdefg").unwrap().collect();
+        assert_eq!(ids.len(), 64);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids
+            .iter()
+            .all(|id| id.is_valid() && id.starts_with("abcdefg")));
+        assert_eq!(ids[0], "abcdefg-");
+        assert_eq!(ids[63], "abcdefgz");
+
+        let block = TinyId::iter_with_prefix("z_").unwrap();
+        assert_eq!(block.len(), 64usize.pow(6));
+        assert_eq!(block.first_id(), "z_------");
+        assert_eq!(block.last_id(), "z_zzzzzz");
+
+        let full: Vec<TinyId> = TinyId::iter_with_prefix("A1b2C3d4").unwrap().collect();
+        assert_eq!(full, [TinyId::from_str_unchecked("A1b2C3d4")]);
+        assert_eq!(TinyId::iter_with_prefix("").unwrap().size(), KEYSPACE);
+
+        assert_eq!(
+            TinyId::iter_with_prefix("abcdefghi"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::iter_with_prefix("a!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn between() {
+        let first = TinyId::from_index_wrapping(0);
+        let last = TinyId::from_index_wrapping(KEYSPACE - 1);
+        let mid = TinyId::between(first, last).unwrap();
+        assert_eq!(mid.to_index(), Some((KEYSPACE - 1) / 2));
+        assert_eq!(TinyId::between(last, first), Some(mid));
+        assert_eq!(TinyId::between(first, first), None);
+        assert_eq!(TinyId::between(first, first.wrapping_add(1)), None);
+        assert_eq!(
+            TinyId::between(first, first.wrapping_add(2)),
+            Some(first.wrapping_add(1))
+        );
+        assert_eq!(TinyId::between(TinyId::null(), last), None);
+        assert_eq!(TinyId::between(first, TinyId::null()), None);
+
+        for _ in 0..1000 {
+            let (a, b) = (TinyId::random(), TinyId::random());
+            if let Some(mid) = TinyId::between(a, b) {
+                assert!(a.min(b) < mid && mid < a.max(b));
+            }
+        }
+
+        let (mut low, high) = (first, first.wrapping_add(1 << 20));
+        let mut inserted = 0;
+        while let Some(mid) = TinyId::between(low, high) {
+            low = mid;
+            inserted += 1;
+        }
+        assert_eq!(inserted, 20);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn prefix_bounds() {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let map: BTreeMap<TinyId, usize> = (0..10_000).map(|i| (TinyId::random(), i)).collect();
+        for prefix in ["", "a", "zz", "-_", "A1b2"] {
+            let found: Vec<TinyId> = map
+                .range(TinyId::prefix_bounds(prefix).unwrap())
+                .map(|(&id, _)| id)
+                .collect();
+            let expected: Vec<TinyId> = map
+                .keys()
+                .copied()
+                .filter(|id| id.starts_with(prefix))
+                .collect();
+            assert_eq!(found, expected, "{prefix}");
+        }
+
+        let full = TinyId::from_str_unchecked("A1b2C3d4");
+        let with_invalid = TinyId::from_bytes_unchecked(*b"zz\0\0\0\0\0\0");
+        let set: BTreeSet<TinyId> = [full, full.wrapping_add(1), with_invalid].into();
+        let range = |prefix| set.range(TinyId::prefix_bounds(prefix).unwrap());
+        assert_eq!(range("A1b2C3d4").collect::<Vec<_>>(), [&full]);
+        assert_eq!(range("zz").collect::<Vec<_>>(), [&with_invalid]);
+
+        assert_eq!(
+            TinyId::prefix_bounds("abcdefghi"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::prefix_bounds("a!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
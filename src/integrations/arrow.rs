@@ -0,0 +1,150 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Conversions between columns of [`TinyId`]s and Arrow arrays, so ID columns can move into
+//! `DataFusion`, Parquet, etc. without stringifying each value. In both directions, null
+//! [`TinyId`]s and Arrow nulls map to each other.
+
+use arrow_array::{FixedSizeBinaryArray, UInt64Array};
+use arrow_buffer::{Buffer, NullBuffer};
+
+use crate::{TinyId, TinyIdError};
+
+/// The byte width of a [`TinyId`] in a [`FixedSizeBinaryArray`].
+const WIDTH: i32 = 8;
+
+impl TinyId {
+    /// Collect `ids` into a [`FixedSizeBinaryArray`] of width 8, holding the bytes of each ID.
+    /// Null IDs become Arrow nulls.
+    ///
+    /// ## Example
+    /// ```
+    /// use arrow_array::Array;
+    /// use tinyid::TinyId;
+    ///
+    /// let ids = vec![TinyId::random(), TinyId::null(), TinyId::random()];
+    /// let array = TinyId::to_arrow_binary(ids.iter().copied());
+    /// assert_eq!(array.len(), 3);
+    /// assert!(array.is_null(1));
+    /// assert_eq!(TinyId::from_arrow_binary(&array), Ok(ids));
+    /// ```
+    #[must_use]
+    pub fn to_arrow_binary(ids: impl IntoIterator<Item = TinyId>) -> FixedSizeBinaryArray {
+        let mut values = Vec::new();
+        let mut valid = Vec::new();
+        for id in ids {
+            values.extend_from_slice(&id.data);
+            valid.push(!id.is_null());
+        }
+        let nulls = Some(NullBuffer::from(valid)).filter(|nulls| nulls.null_count() > 0);
+        FixedSizeBinaryArray::new(WIDTH, Buffer::from_vec(values), nulls)
+    }
+
+    /// Read the IDs back out of a [`FixedSizeBinaryArray`] written by
+    /// [`TinyId::to_arrow_binary`]. Arrow nulls become null IDs.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if the array's values are not 8 bytes wide.
+    /// - [`TinyIdError::InvalidCharacters`] if any value is not a valid ID.
+    pub fn from_arrow_binary(array: &FixedSizeBinaryArray) -> Result<Vec<TinyId>, TinyIdError> {
+        if array.value_length() != WIDTH {
+            return Err(TinyIdError::InvalidLength);
+        }
+        array
+            .iter()
+            .map(|value| match value {
+                Some(bytes) => TinyId::from_bytes(bytes.try_into()?),
+                None => Ok(TinyId::null()),
+            })
+            .collect()
+    }
+
+    /// Collect `ids` into a [`UInt64Array`] of their [`TinyId::to_u64`] values. Null IDs become
+    /// Arrow nulls.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let ids = vec![TinyId::random(), TinyId::random()];
+    /// let array = TinyId::to_arrow_u64(ids.iter().copied());
+    /// assert_eq!(array.value(0), ids[0].to_u64());
+    /// assert_eq!(TinyId::from_arrow_u64(&array), Ok(ids));
+    /// ```
+    #[must_use]
+    pub fn to_arrow_u64(ids: impl IntoIterator<Item = TinyId>) -> UInt64Array {
+        ids.into_iter()
+            .map(|id| (!id.is_null()).then(|| id.to_u64()))
+            .collect()
+    }
+
+    /// Read the IDs back out of a [`UInt64Array`] written by [`TinyId::to_arrow_u64`]. Arrow
+    /// nulls become null IDs.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if any value is not a valid ID.
+    pub fn from_arrow_u64(array: &UInt64Array) -> Result<Vec<TinyId>, TinyIdError> {
+        array
+            .iter()
+            .map(|value| value.map_or(Ok(TinyId::null()), TinyId::from_u64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn binary_roundtrip() {
+        let mut ids = (0..100).map(|_| TinyId::random()).collect::<Vec<_>>();
+        ids[10] = TinyId::null();
+        let array = TinyId::to_arrow_binary(ids.iter().copied());
+        assert_eq!(array.len(), 100);
+        assert_eq!(array.null_count(), 1);
+        assert_eq!(array.value(0), ids[0].to_bytes());
+        assert_eq!(TinyId::from_arrow_binary(&array), Ok(ids));
+
+        let empty = TinyId::to_arrow_binary(std::iter::empty());
+        assert!(empty.is_empty());
+        assert_eq!(TinyId::from_arrow_binary(&empty), Ok(Vec::new()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn binary_errors() {
+        let wide = FixedSizeBinaryArray::try_from_iter([b"abcdefghi"].into_iter()).unwrap();
+        assert_eq!(
+            TinyId::from_arrow_binary(&wide),
+            Err(TinyIdError::InvalidLength)
+        );
+        let invalid = FixedSizeBinaryArray::try_from_iter([b"abc!efgh"].into_iter()).unwrap();
+        assert_eq!(
+            TinyId::from_arrow_binary(&invalid),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn u64_roundtrip() {
+        let mut ids = (0..100).map(|_| TinyId::random()).collect::<Vec<_>>();
+        ids[42] = TinyId::null();
+        let array = TinyId::to_arrow_u64(ids.iter().copied());
+        assert_eq!(array.len(), 100);
+        assert!(array.is_null(42));
+        assert_eq!(array.value(1), ids[1].to_u64());
+        assert_eq!(TinyId::from_arrow_u64(&array), Ok(ids));
+
+        let invalid = UInt64Array::from(vec![1]);
+        assert_eq!(
+            TinyId::from_arrow_u64(&invalid),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
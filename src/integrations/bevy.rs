@@ -0,0 +1,73 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implements `Reflect`, `FromReflect` and `GetTypeRegistration` for [`TinyId`] as an opaque value,
+//! so it can be a field of Bevy components. Register it with `app.register_type::<TinyId>()`;
+//! with the `serde` feature the registration also carries `ReflectSerialize` and
+//! `ReflectDeserialize`, so IDs survive scene serialization as their usual serde form.
+
+use bevy_reflect::impl_reflect_opaque;
+#[cfg(feature = "serde")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::TinyId;
+
+#[cfg(not(feature = "serde"))]
+impl_reflect_opaque!((in tinyid) TinyId(Clone, Debug, Hash, PartialEq));
+#[cfg(feature = "serde")]
+impl_reflect_opaque!((in tinyid) TinyId(Clone, Debug, Hash, PartialEq, Serialize, Deserialize));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::{FromReflect, PartialReflect, Reflect, TypePath, TypeRegistry};
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn reflect() {
+        let id = TinyId::random();
+        let reflected: &dyn Reflect = &id;
+        assert_eq!(reflected.downcast_ref::<TinyId>(), Some(&id));
+        assert_eq!(reflected.reflect_partial_eq(&id), Some(true));
+        assert_eq!(reflected.reflect_partial_eq(&TinyId::random()), Some(false));
+        assert_eq!(TinyId::from_reflect(reflected), Some(id));
+        assert_eq!(TinyId::type_path(), "tinyid::TinyId");
+        assert_eq!(TinyId::short_type_path(), "TinyId");
+
+        let mut other = TinyId::null();
+        other.try_apply(reflected).unwrap();
+        assert_eq!(other, id);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn register() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<TinyId>();
+        assert!(registry.get_with_type_path("tinyid::TinyId").is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn reflect_serde() {
+        use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<TinyId>();
+        let id = TinyId::random();
+        let json = serde_json::to_string(&TypedReflectSerializer::new(&id, &registry)).unwrap();
+        assert_eq!(json, serde_json::to_string(&id).unwrap());
+
+        let registration = registry.get(std::any::TypeId::of::<TinyId>()).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut de)
+            .unwrap();
+        assert_eq!(TinyId::from_reflect(value.as_ref()), Some(id));
+    }
+}
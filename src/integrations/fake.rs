@@ -0,0 +1,36 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use fake::{Dummy, Faker, Rng};
+
+use crate::TinyId;
+
+/// Allows `fake` to generate valid random [`TinyId`]s, e.g. `Faker.fake::<TinyId>()` or as a
+/// field of a struct deriving `Dummy`.
+impl Dummy<Faker> for TinyId {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        TinyId::from_random_bits(rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::Fake;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn dummy() {
+        for _ in 0..100 {
+            let id: TinyId = Faker.fake();
+            assert!(id.is_valid());
+        }
+
+        let ids: Vec<TinyId> = fake::vec![TinyId; 3..5];
+        assert!(ids.len() >= 3 && ids.len() < 5);
+        assert!(ids.iter().all(|id| id.is_valid()));
+    }
+}
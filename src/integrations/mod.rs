@@ -0,0 +1,37 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Trait implementations for third-party crates. Each submodule is gated behind the
+//! feature flag of the same name.
+
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "bevy")]
+mod bevy;
+#[cfg(feature = "fake")]
+mod fake;
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "okapi")]
+mod okapi;
+#[cfg(feature = "poem-openapi")]
+mod poem_openapi;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "scylla")]
+mod scylla;
+#[cfg(feature = "serde_json")]
+mod serde_json;
+#[cfg(feature = "uniffi")]
+mod uniffi;
+#[cfg(feature = "valuable")]
+mod valuable;
+#[cfg(feature = "warp")]
+pub(crate) mod warp;
+
+/// The pattern every valid [`TinyId`](crate::TinyId) matches, for `OpenAPI` schemas.
+#[cfg(any(feature = "okapi", feature = "poem-openapi"))]
+const PATTERN: &str = "^[A-Za-z0-9_-]{8}$";
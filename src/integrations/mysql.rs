@@ -0,0 +1,95 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Conversions between [`TinyId`] and `mysql_common`'s `Value`, so the `mysql` and `mysql_async`
+//! crates can bind and read IDs directly. IDs are bound as their 8 bytes (suitable for
+//! `CHAR(8)`, `BINARY(8)` or `VARCHAR` columns), and can be read back from those or from integer
+//! columns holding their [`TinyId::to_u64`] value. The null ID maps to SQL `NULL` and back.
+
+use mysql_common::value::{
+    convert::{FromValue, FromValueError},
+    Value,
+};
+
+use crate::{TinyId, TinyIdError};
+
+impl From<TinyId> for Value {
+    fn from(id: TinyId) -> Self {
+        if id.is_null() {
+            Value::NULL
+        } else {
+            Value::Bytes(id.to_bytes().to_vec())
+        }
+    }
+}
+
+impl TryFrom<Value> for TinyId {
+    type Error = FromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let id = match &value {
+            Value::NULL => Ok(TinyId::null()),
+            Value::Bytes(bytes) => TinyId::from_key_bytes(bytes),
+            Value::UInt(n) => TinyId::from_u64(*n),
+            Value::Int(n) => u64::try_from(*n)
+                .map_err(|_| TinyIdError::InvalidCharacters)
+                .and_then(TinyId::from_u64),
+            _ => Err(TinyIdError::InvalidCharacters),
+        };
+        id.map_err(|_| FromValueError(value))
+    }
+}
+
+impl FromValue for TinyId {
+    type Intermediate = TinyId;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn to_value() {
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        assert_eq!(Value::from(id), Value::Bytes(b"abcdefgh".to_vec()));
+        assert_eq!(Value::from(TinyId::null()), Value::NULL);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn from_value() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert_eq!(TinyId::from_value(Value::from(id)), id);
+            assert_eq!(TinyId::from_value(Value::UInt(id.to_u64())), id);
+        }
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        #[allow(clippy::cast_possible_wrap)]
+        let signed = Value::Int(id.to_u64() as i64);
+        assert_eq!(TinyId::from_value(signed), id);
+        assert_eq!(TinyId::from_value(Value::NULL), TinyId::null());
+        assert_eq!(Option::<TinyId>::from_value(Value::NULL), None);
+        assert_eq!(Option::<TinyId>::from_value(Value::from(id)), Some(id));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn from_value_errors() {
+        for value in [
+            Value::Bytes(b"abc".to_vec()),
+            Value::Bytes(b"abc!efgh".to_vec()),
+            Value::UInt(1),
+            Value::Int(-1),
+            Value::Double(1.0),
+        ] {
+            assert_eq!(
+                TinyId::from_value_opt(value.clone()),
+                Err(FromValueError(value))
+            );
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implements `schemars`' `JsonSchema` (the version `okapi` and `rocket_okapi` are built on) for
+//! [`TinyId`] and [`StringTinyId`], so Rocket routes using them get a correct `OpenAPI` schema.
+//!
+//! Each schema describes the type's actual serde form: a [`TinyId`] is an object holding its 8 raw
+//! bytes, while a [`StringTinyId`] is documented as a validated 8-character string. IDs in JSON
+//! bodies should normally use [`StringTinyId`].
+
+use std::borrow::Cow;
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, ObjectValidation, Schema, SchemaObject, StringValidation},
+    JsonSchema,
+};
+
+use crate::{StringTinyId, TinyId};
+
+impl JsonSchema for TinyId {
+    fn schema_name() -> String {
+        "TinyId".to_string()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("tinyid::TinyId")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                properties: [("data".to_string(), gen.subschema_for::<[u8; 8]>())].into(),
+                required: ["data".to_string()].into(),
+                ..ObjectValidation::default()
+            })),
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "A TinyId as its 8 raw bytes. See StringTinyId for the string form."
+                        .to_string(),
+                ),
+                ..Metadata::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+impl JsonSchema for StringTinyId {
+    fn schema_name() -> String {
+        "StringTinyId".to_string()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("tinyid::StringTinyId")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("tinyid".to_string()),
+            string: Some(Box::new(StringValidation {
+                max_length: Some(8),
+                min_length: Some(8),
+                pattern: Some(super::PATTERN.to_string()),
+            })),
+            metadata: Some(Box::new(Metadata {
+                description: Some("An 8-character TinyId.".to_string()),
+                examples: vec![serde_json::Value::String("abcd_-12".to_string())],
+                ..Metadata::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn string_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(StringTinyId)).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["format"], "tinyid");
+        assert_eq!(schema["minLength"], 8);
+        assert_eq!(schema["maxLength"], 8);
+        assert_eq!(schema["pattern"], super::super::PATTERN);
+        assert_eq!(schema["examples"], json!(["abcd_-12"]));
+        assert!(serde_json::to_value(StringTinyId::default())
+            .unwrap()
+            .is_string());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn bytes_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(TinyId)).unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["data"]));
+        let data = &schema["properties"]["data"];
+        assert_eq!(data["type"], "array");
+        assert_eq!(data["minItems"], 8);
+        assert_eq!(data["maxItems"], 8);
+        assert_eq!(data["items"]["type"], "integer");
+
+        let value = serde_json::to_value(TinyId::random()).unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 1);
+        assert_eq!(value["data"].as_array().unwrap().len(), 8);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn referenced_from_fields() {
+        #[derive(serde::Serialize)]
+        struct User {
+            id: StringTinyId,
+        }
+
+        impl JsonSchema for User {
+            fn schema_name() -> String {
+                "User".to_string()
+            }
+
+            fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                let mut schema = SchemaObject {
+                    instance_type: Some(InstanceType::Object.into()),
+                    ..SchemaObject::default()
+                };
+                schema
+                    .object()
+                    .properties
+                    .insert("id".to_string(), gen.subschema_for::<StringTinyId>());
+                schema.into()
+            }
+        }
+
+        let schema = serde_json::to_value(schemars::schema_for!(User)).unwrap();
+        assert_eq!(
+            schema["properties"]["id"]["$ref"],
+            "#/definitions/StringTinyId"
+        );
+        assert_eq!(schema["definitions"]["StringTinyId"]["type"], "string");
+
+        let user = User {
+            id: TinyId::from_str_unchecked("abcd1234").into(),
+        };
+        assert_eq!(
+            serde_json::to_value(user).unwrap(),
+            json!({ "id": "abcd1234" })
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implements `poem-openapi`'s `Type`, `ParseFromJSON`, `ToJSON` and `ParseFromParameter` for
+//! [`TinyId`], so it can be used in request bodies and path/query parameters. Incoming IDs are
+//! validated, and the schema documents them as 8-character strings.
+
+use std::borrow::Cow;
+
+use poem_openapi::{
+    registry::{MetaSchema, MetaSchemaRef},
+    types::{ParseError, ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type},
+};
+use serde_json::Value;
+
+use super::PATTERN;
+use crate::TinyId;
+
+impl Type for TinyId {
+    const IS_REQUIRED: bool = true;
+
+    type RawValueType = Self;
+
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        "string_tinyid".into()
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Inline(Box::new(MetaSchema {
+            min_length: Some(8),
+            max_length: Some(8),
+            pattern: Some(PATTERN.to_string()),
+            example: Some(Value::String("abcd_-12".to_string())),
+            ..MetaSchema::new_with_format("string", "tinyid")
+        }))
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(self.as_raw_value().into_iter())
+    }
+}
+
+impl ParseFromJSON for TinyId {
+    fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+        match value.unwrap_or_default() {
+            Value::String(value) => Ok(value.parse()?),
+            value => Err(ParseError::expected_type(value)),
+        }
+    }
+}
+
+impl ParseFromParameter for TinyId {
+    fn parse_from_parameter(value: &str) -> ParseResult<Self> {
+        Ok(value.parse()?)
+    }
+}
+
+impl ToJSON for TinyId {
+    fn to_json(&self) -> Option<Value> {
+        Some(Value::String(self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn json() {
+        let id = TinyId::random();
+        let json = id.to_json().unwrap();
+        assert_eq!(json, Value::String(id.to_string()));
+        assert_eq!(TinyId::parse_from_json(Some(json)).unwrap(), id);
+        assert!(TinyId::parse_from_json(None).is_err());
+        assert!(TinyId::parse_from_json(Some(Value::from(1))).is_err());
+        assert!(TinyId::parse_from_json(Some(Value::from("abc!efgh"))).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn parameter() {
+        assert_eq!(
+            TinyId::parse_from_parameter("abcdefgh").unwrap(),
+            TinyId::from_str_unchecked("abcdefgh")
+        );
+        let err = TinyId::parse_from_parameter("abc").unwrap_err();
+        assert!(
+            err.message().contains("Invalid length"),
+            "{}",
+            err.message()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn schema() {
+        let MetaSchemaRef::Inline(schema) = TinyId::schema_ref() else {
+            panic!("schema should be inline");
+        };
+        assert_eq!(schema.ty, "string");
+        assert_eq!(schema.format, Some("tinyid"));
+        assert_eq!(schema.min_length, Some(8));
+        assert_eq!(schema.max_length, Some(8));
+        assert_eq!(schema.pattern.as_deref(), Some(PATTERN));
+        let example = schema.example.unwrap();
+        assert!(TinyId::parse_from_json(Some(example)).unwrap().is_valid());
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{feistel::Feistel48, index::KEYSPACE, TinyId};
+
+/// Allows generating random [`TinyId`]s from any [`Rng`], e.g. `rng.gen::<TinyId>()`.
+impl Distribution<TinyId> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TinyId {
+        TinyId::from_random_bits(rng.gen())
+    }
+}
+
+impl TinyId {
+    /// Draw `n` distinct [`TinyId`]s uniformly from the whole keyspace, using `rng` as the source
+    /// of randomness. Asking for more than the `64^8` valid IDs gives every valid ID once.
+    ///
+    /// The IDs are a run of consecutive positions (from a random start) passed through a keyed
+    /// permutation of the keyspace (with a random key), so they are guaranteed to be duplicate-free
+    /// without keeping a set of the IDs drawn so far.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::collections::HashSet;
+    /// use tinyid::TinyId;
+    ///
+    /// let ids = TinyId::sample_unique(1000, &mut rand::thread_rng());
+    /// assert_eq!(ids.len(), 1000);
+    /// assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 1000);
+    /// ```
+    #[must_use]
+    pub fn sample_unique<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<TinyId> {
+        let cipher = Feistel48::new(rng.gen());
+        let start = rng.gen_range(0..KEYSPACE);
+        (0..KEYSPACE)
+            .take(n)
+            .map(|i| TinyId::from_index_wrapping(cipher.permute((start + i) % KEYSPACE)))
+            .collect()
+    }
+
+    /// Create a deterministic RNG seeded from this [`TinyId`], for reproducible per-entity
+    /// procedural generation keyed by the entity's ID. The same ID always gives an RNG producing
+    /// the same sequence.
+    ///
+    /// Any [`SeedableRng`] can be used; choose one with a stable output (e.g. `rand_chacha`'s
+    /// `ChaCha8Rng`) if the sequence must not change between versions of `rand`, which
+    /// [`StdRng`] does not promise.
+    ///
+    /// ## Example
+    /// ```
+    /// use rand::{rngs::StdRng, Rng};
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let mut a: StdRng = id.to_seeded_rng();
+    /// let mut b = StdRng::from(id);
+    /// assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+    /// ```
+    #[must_use]
+    pub fn to_seeded_rng<R: SeedableRng>(&self) -> R {
+        R::seed_from_u64(self.to_u64())
+    }
+}
+
+/// Seeds a [`StdRng`] from a [`TinyId`], see [`TinyId::to_seeded_rng`].
+impl From<TinyId> for StdRng {
+    fn from(id: TinyId) -> Self {
+        id.to_seeded_rng()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn standard_distribution() {
+        let mut rng = StepRng::new(0, 0x0101_0101_0101_0101);
+        let id: TinyId = rng.gen();
+        assert!(id.is_valid());
+        assert_eq!(id.to_string(), "aaaaaaaa");
+        let id: TinyId = rng.gen();
+        assert_eq!(id.to_string(), "bbbbbbbb");
+
+        let ids: Vec<TinyId> = rand::thread_rng().sample_iter(Standard).take(100).collect();
+        assert!(ids.iter().all(|id| id.is_valid()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn sample_unique() {
+        use std::collections::HashSet;
+
+        let mut rng = rand::thread_rng();
+        assert!(TinyId::sample_unique(0, &mut rng).is_empty());
+        let ids = TinyId::sample_unique(100_000, &mut rng);
+        assert_eq!(ids.len(), 100_000);
+        assert!(ids.iter().all(|id| id.is_valid()));
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+        assert_ne!(TinyId::sample_unique(10, &mut rng), ids[..10]);
+
+        let mut a = StepRng::new(7, 11);
+        let mut b = StepRng::new(7, 11);
+        assert_eq!(
+            TinyId::sample_unique(10, &mut a),
+            TinyId::sample_unique(10, &mut b)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn seeded_rng() {
+        let draw = |mut rng: StdRng| (0..10).map(|_| rng.gen::<TinyId>()).collect::<Vec<_>>();
+        let id = TinyId::random();
+        let ids = draw(id.to_seeded_rng());
+        assert_eq!(ids, draw(StdRng::from(id)));
+        assert_ne!(ids, draw(id.wrapping_add(1).to_seeded_rng()));
+        assert_eq!(
+            draw(TinyId::null().to_seeded_rng()),
+            draw(StdRng::seed_from_u64(0))
+        );
+    }
+}
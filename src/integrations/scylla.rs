@@ -0,0 +1,158 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implements the `ScyllaDB` driver's `SerializeValue` and `DeserializeValue` for [`TinyId`], so it
+//! can be bound to prepared statements and read from rows directly. IDs are stored as their
+//! string form in `text`/`ascii` columns, or as their [`TinyId::to_u64`] value in `bigint`
+//! columns. The null ID is stored as a CQL `NULL`, and reading `NULL` gives the null ID.
+
+use scylla_cql_core::{
+    deserialize::{value::DeserializeValue, DeserializationError, FrameSlice, TypeCheckError},
+    frame::response::result::{ColumnType, NativeType},
+    serialize::{
+        value::SerializeValue,
+        writers::{CellWriter, WrittenCellProof},
+        SerializationError,
+    },
+};
+
+use crate::{TinyId, TinyIdError};
+
+/// Whether a [`TinyId`] can be stored in a column of the given type.
+fn is_supported(typ: &ColumnType<'_>) -> bool {
+    matches!(
+        typ,
+        ColumnType::Native(NativeType::Text | NativeType::Ascii | NativeType::BigInt)
+    )
+}
+
+/// The error for a column whose type can't hold a [`TinyId`].
+fn unsupported(typ: &ColumnType<'_>) -> TinyIdError {
+    TinyIdError::Conversion(format!(
+        "TinyId can only be stored in text, ascii or bigint columns, not {typ:?}"
+    ))
+}
+
+impl SerializeValue for TinyId {
+    #[allow(clippy::cast_possible_wrap)]
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType<'_>,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        if !is_supported(typ) {
+            return Err(SerializationError::new(unsupported(typ)));
+        }
+        if self.is_null() {
+            return Ok(writer.set_null());
+        }
+        match typ {
+            ColumnType::Native(NativeType::BigInt) => (self.to_u64() as i64).serialize(typ, writer),
+            _ => self.to_string().serialize(typ, writer),
+        }
+    }
+}
+
+impl<'frame, 'metadata> DeserializeValue<'frame, 'metadata> for TinyId {
+    fn type_check(typ: &ColumnType<'_>) -> Result<(), TypeCheckError> {
+        if is_supported(typ) {
+            Ok(())
+        } else {
+            Err(TypeCheckError::new(unsupported(typ)))
+        }
+    }
+
+    #[allow(clippy::cast_sign_loss)]
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        if v.is_none() {
+            return Ok(TinyId::null());
+        }
+        let id = match typ {
+            ColumnType::Native(NativeType::BigInt) => {
+                TinyId::from_u64(i64::deserialize(typ, v)? as u64)
+            }
+            _ => <&str>::deserialize(typ, v)?.parse(),
+        };
+        id.map_err(DeserializationError::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: ColumnType<'static> = ColumnType::Native(NativeType::Text);
+    const BIGINT: ColumnType<'static> = ColumnType::Native(NativeType::BigInt);
+
+    fn write(id: TinyId, typ: &ColumnType<'_>) -> Result<Vec<u8>, SerializationError> {
+        let mut buf = Vec::new();
+        id.serialize(typ, CellWriter::new(&mut buf))?;
+        Ok(buf)
+    }
+
+    fn read(buf: &[u8], typ: &'static ColumnType<'static>) -> Result<TinyId, DeserializationError> {
+        // Skip the 4-byte length prefix written by `CellWriter`; a negative length means NULL.
+        let len = i32::from_be_bytes(buf[..4].try_into().unwrap());
+        let value = (len >= 0).then(|| FrameSlice::new_borrowed(&buf[4..]));
+        TinyId::deserialize(typ, value)
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn text_roundtrip() {
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        let buf = write(id, &TEXT).unwrap();
+        assert_eq!(&buf[4..], b"abcdefgh");
+        assert_eq!(read(&buf, &TEXT).unwrap(), id);
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert_eq!(read(&write(id, &TEXT).unwrap(), &TEXT).unwrap(), id);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn bigint_roundtrip() {
+        let id = TinyId::random();
+        let buf = write(id, &BIGINT).unwrap();
+        assert_eq!(&buf[4..], id.to_u64().to_be_bytes());
+        assert_eq!(read(&buf, &BIGINT).unwrap(), id);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn null() {
+        for typ in [&TEXT, &BIGINT] {
+            let buf = write(TinyId::null(), typ).unwrap();
+            assert_eq!(buf, (-1i32).to_be_bytes());
+            assert_eq!(read(&buf, typ).unwrap(), TinyId::null());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn errors() {
+        let blob = ColumnType::Native(NativeType::Blob);
+        assert!(write(TinyId::random(), &blob).is_err());
+        assert!(<TinyId as DeserializeValue>::type_check(&blob).is_err());
+        assert!(<TinyId as DeserializeValue>::type_check(&TEXT).is_ok());
+        assert!(<TinyId as DeserializeValue>::type_check(&BIGINT).is_ok());
+
+        let mut buf = Vec::new();
+        "not an id"
+            .serialize(&TEXT, CellWriter::new(&mut buf))
+            .unwrap();
+        let err = read(&buf, &TEXT).unwrap_err();
+        assert!(err.downcast_ref::<TinyIdError>().is_some());
+
+        let mut buf = Vec::new();
+        1i64.serialize(&BIGINT, CellWriter::new(&mut buf)).unwrap();
+        assert!(read(&buf, &BIGINT).is_err());
+    }
+}
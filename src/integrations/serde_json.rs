@@ -0,0 +1,110 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Conversions between [`TinyId`] and `serde_json::Value`, for code that builds or inspects JSON
+//! dynamically. An ID is converted to a JSON string holding its display form.
+//!
+//! **Note:** this is *not* the form produced by [`TinyId`]'s serde implementation, which
+//! `serde_json::to_value` uses: that is an object holding the raw bytes (`{"data":[...]}`).
+//! Converting back from a [`Value`] accepts both forms, but code comparing JSON produced both
+//! ways should use [`serde_helpers::string`](crate::serde_helpers::string) for its ID fields.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{TinyId, TinyIdError};
+
+impl From<TinyId> for Value {
+    fn from(id: TinyId) -> Self {
+        Value::String(id.to_string())
+    }
+}
+
+/// Reads a [`TinyId`] from a JSON string, or from the object produced by serializing a [`TinyId`].
+///
+/// ## Errors
+/// - [`TinyIdError::Conversion`] if the value is neither a string nor a serialized [`TinyId`].
+/// - [`TinyIdError::InvalidLength`] or [`TinyIdError::InvalidCharacters`] if the string is not a
+///   valid ID.
+///
+/// ## Example
+/// ```
+/// use serde_json::{json, Value};
+/// use tinyid::TinyId;
+///
+/// let id = TinyId::random();
+/// let body = json!({ "id": Value::from(id) });
+/// assert_eq!(body["id"], id.to_string());
+/// assert_eq!(TinyId::try_from(&body["id"]), Ok(id));
+/// assert!(TinyId::try_from(&json!(12)).is_err());
+///
+/// let serialized = serde_json::to_value(id).unwrap();
+/// assert_ne!(serialized, Value::from(id));
+/// assert_eq!(TinyId::try_from(&serialized), Ok(id));
+/// ```
+impl TryFrom<&Value> for TinyId {
+    type Error = TinyIdError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => s.parse(),
+            Value::Object(_) => TinyId::deserialize(value)
+                .map_err(|err| TinyIdError::Conversion(err.to_string()))
+                .and_then(|id| TinyId::from_bytes(id.to_bytes())),
+            other => Err(TinyIdError::Conversion(format!(
+                "expected a JSON string for TinyId, found {other}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn roundtrip() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            let value = Value::from(id);
+            assert_eq!(value, Value::String(id.to_string()));
+            assert_eq!(TinyId::try_from(&value), Ok(id));
+
+            let serialized = serde_json::to_value(id).unwrap();
+            assert!(serialized.is_object());
+            assert_eq!(TinyId::try_from(&serialized), Ok(id));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn errors() {
+        assert_eq!(
+            TinyId::try_from(&Value::from("abc")),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::try_from(&Value::from("abc!efgh")),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::try_from(&serde_json::to_value(TinyId::null()).unwrap()),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        for value in [
+            Value::Null,
+            Value::from(12),
+            serde_json::json!(["abcdefgh"]),
+            serde_json::json!({ "id": "abcdefgh" }),
+        ] {
+            assert!(matches!(
+                TinyId::try_from(&value),
+                Err(TinyIdError::Conversion(_))
+            ));
+        }
+    }
+}
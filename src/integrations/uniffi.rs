@@ -0,0 +1,76 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! `UniFFI` exports, so Kotlin and Swift apps can construct, parse and format [`TinyId`]s with the
+//! same rules as the Rust code. [`TinyId`] crosses the FFI boundary as its string form, and is
+//! validated whenever it is passed back in; [`TinyIdError`] is a flat error carrying its
+//! `Display` message.
+
+use crate::{TinyId, TinyIdError};
+
+uniffi::custom_type!(TinyId, String, {
+    lower: |id| id.to_string(),
+    try_lift: |s| Ok(s.parse::<TinyId>()?),
+});
+
+/// Generate a new random [`TinyId`].
+#[uniffi::export]
+fn tinyid_random() -> TinyId {
+    TinyId::random()
+}
+
+/// Parse `input` as a [`TinyId`].
+#[uniffi::export]
+fn tinyid_parse(input: &str) -> Result<TinyId, TinyIdError> {
+    input.parse()
+}
+
+/// Check whether `input` is a valid [`TinyId`].
+#[uniffi::export]
+fn tinyid_is_valid(input: &str) -> bool {
+    input.parse::<TinyId>().is_ok()
+}
+
+/// Create the [`TinyId`] holding the given [`TinyId::to_u64`] value.
+#[uniffi::export]
+fn tinyid_from_u64(value: u64) -> Result<TinyId, TinyIdError> {
+    TinyId::from_u64(value)
+}
+
+/// Get the `u64` value of `id` (see [`TinyId::to_u64`]).
+#[uniffi::export]
+fn tinyid_to_u64(id: TinyId) -> u64 {
+    id.to_u64()
+}
+
+/// Format `id` in chunks of `chunk_size` letters separated by `separator` (see
+/// [`TinyId::display_chunked`]).
+#[uniffi::export]
+fn tinyid_format_chunked(id: TinyId, chunk_size: u32, separator: &str) -> String {
+    id.display_chunked(chunk_size as usize, separator)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn exports() {
+        let id = tinyid_random();
+        assert!(id.is_valid());
+        assert!(tinyid_is_valid(&id.to_string()));
+        assert!(!tinyid_is_valid("abc!efgh"));
+        assert_eq!(tinyid_parse(&id.to_string()), Ok(id));
+        assert_eq!(tinyid_parse("abc"), Err(TinyIdError::InvalidLength));
+        assert_eq!(tinyid_from_u64(tinyid_to_u64(id)), Ok(id));
+        assert_eq!(
+            tinyid_format_chunked(TinyId::from_str_unchecked("abcdefgh"), 4, " "),
+            "abcd efgh"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implements `Valuable` for [`TinyId`], so structured loggers (e.g. `tracing` with its
+//! `valuable` support, `tracing::info!(id = id.as_value())`) record IDs as string fields,
+//! borrowed straight from the ID without formatting or allocating.
+
+use valuable::{Valuable, Value, Visit};
+
+use crate::TinyId;
+
+impl Valuable for TinyId {
+    /// The ID as a string. Every valid (and the null) ID is ASCII; an ID created unchecked from
+    /// bytes which are not UTF-8 is given as its list of bytes instead.
+    fn as_value(&self) -> Value<'_> {
+        match std::str::from_utf8(&self.data) {
+            Ok(s) => Value::String(s),
+            Err(_) => Value::Listable(&self.data),
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn as_value() {
+        let id = TinyId::random();
+        let expected = id.to_string();
+        assert!(matches!(id.as_value(), Value::String(s) if s == expected));
+        assert!(matches!(
+            TinyId::null().as_value(),
+            Value::String("\0\0\0\0\0\0\0\0")
+        ));
+        let garbage = TinyId::from_bytes_unchecked([0xFF; 8]);
+        assert!(matches!(garbage.as_value(), Value::Listable(_)));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn visit() {
+        struct Collect(Option<String>);
+        impl Visit for Collect {
+            fn visit_value(&mut self, value: Value<'_>) {
+                if let Value::String(s) = value {
+                    self.0 = Some(s.to_string());
+                }
+            }
+        }
+
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        let mut collect = Collect(None);
+        valuable::visit(&id, &mut collect);
+        assert_eq!(collect.0.as_deref(), Some("abcdefgh"));
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! `warp` filters for extracting [`TinyId`]s from requests.
+
+use warp::{http::StatusCode, reject::Reject, Filter, Rejection, Reply};
+
+use crate::{TinyId, TinyIdError};
+
+/// The rejection produced by [`tinyid_path`] when a path segment is not a valid [`TinyId`].
+/// [`recover_invalid_tinyid`] turns it into a `400 Bad Request`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidTinyId {
+    segment: String,
+    error: TinyIdError,
+}
+
+impl InvalidTinyId {
+    /// The path segment that failed to parse.
+    #[must_use]
+    pub fn segment(&self) -> &str {
+        &self.segment
+    }
+
+    /// Why the segment failed to parse.
+    #[must_use]
+    pub fn error(&self) -> &TinyIdError {
+        &self.error
+    }
+}
+
+impl std::fmt::Display for InvalidTinyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid TinyId {:?}: {}", self.segment, self.error)
+    }
+}
+
+impl std::error::Error for InvalidTinyId {}
+
+impl Reject for InvalidTinyId {}
+
+/// A filter extracting the next path segment as a validated [`TinyId`], rejecting with
+/// [`InvalidTinyId`] if it is not one. Pair it with [`recover_invalid_tinyid`] to answer such
+/// requests with a `400 Bad Request` and a JSON body describing the problem.
+///
+/// ## Example
+/// ```
+/// use tinyid::{recover_invalid_tinyid, tinyid_path, TinyId};
+/// use warp::Filter;
+///
+/// let route = warp::path("users")
+///     .and(tinyid_path())
+///     .map(|id: TinyId| format!("user {id}"))
+///     .recover(recover_invalid_tinyid);
+/// ```
+#[must_use]
+pub fn tinyid_path() -> impl Filter<Extract = (TinyId,), Error = Rejection> + Copy {
+    warp::path::param::<String>().and_then(|segment: String| async move {
+        segment
+            .parse::<TinyId>()
+            .map_err(|error| warp::reject::custom(InvalidTinyId { segment, error }))
+    })
+}
+
+/// A `recover` handler turning an [`InvalidTinyId`] rejection into a `400 Bad Request` with a
+/// JSON body like `{"error":"invalid_tinyid","segment":"abc","message":"Invalid length"}`. Any
+/// other rejection is passed through unchanged.
+///
+/// ## Errors
+/// Returns `rejection` unchanged if it is not an [`InvalidTinyId`].
+pub async fn recover_invalid_tinyid(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    let Some(invalid) = rejection.find::<InvalidTinyId>() else {
+        return Err(rejection);
+    };
+    let body = serde_json::json!({
+        "error": "invalid_tinyid",
+        "segment": invalid.segment,
+        "message": invalid.error.to_string(),
+    });
+    Ok(warp::reply::with_status(
+        warp::reply::json(&body),
+        StatusCode::BAD_REQUEST,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn extracts_valid_ids() {
+        let filter = warp::path("users").and(tinyid_path());
+        let id = warp::test::request()
+            .path("/users/abcdefgh")
+            .filter(&filter)
+            .await
+            .unwrap();
+        assert_eq!(id, TinyId::from_str_unchecked("abcdefgh"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn rejects_invalid_ids() {
+        let filter = warp::path("users").and(tinyid_path());
+        let rejection = warp::test::request()
+            .path("/users/abc")
+            .filter(&filter)
+            .await
+            .unwrap_err();
+        let invalid = rejection.find::<InvalidTinyId>().unwrap();
+        assert_eq!(invalid.segment(), "abc");
+        assert_eq!(invalid.error(), &TinyIdError::InvalidLength);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn recovers_with_bad_request() {
+        let route = warp::path("users")
+            .and(tinyid_path())
+            .map(|id: TinyId| id.to_string())
+            .recover(recover_invalid_tinyid);
+
+        let ok = warp::test::request()
+            .path("/users/abcdefgh")
+            .reply(&route)
+            .await;
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(ok.body(), "abcdefgh");
+
+        let bad = warp::test::request()
+            .path("/users/abc!efgh")
+            .reply(&route)
+            .await;
+        assert_eq!(bad.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(bad.body()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": "invalid_tinyid",
+                "segment": "abc!efgh",
+                "message": "Invalid characters",
+            })
+        );
+
+        let missing = warp::test::request().path("/other").reply(&route).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Key encoding for ordered key-value stores such as `RocksDB` and sled, which compare keys
+//! byte-wise. The key of an ID is simply its 8 bytes (see [`TinyId::sort_key`]), so keys sort in
+//! the same order as the IDs themselves.
+
+use crate::{TinyId, TinyIdError};
+
+/// The exclusive upper bound of an empty prefix: one past the greatest letter, `z`.
+const PAST_LAST_LETTER: u8 = b'z' + 1;
+
+/// The least letter, used to pad prefixes out to a full key.
+const FIRST_LETTER: u8 = b'-';
+
+impl TinyId {
+    /// Borrow the key bytes of this [`TinyId`], to pass to a store without copying or allocating.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert_eq!(id.as_key_bytes(), b"abcdefgh");
+    /// assert_eq!(TinyId::from_key_bytes(id.as_key_bytes()), Ok(id));
+    /// ```
+    #[must_use]
+    pub fn as_key_bytes(&self) -> &[u8; 8] {
+        &self.data
+    }
+
+    /// Read a [`TinyId`] back from key bytes returned by a store.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `bytes` is not 8 bytes long.
+    /// - [`TinyIdError::InvalidCharacters`] if `bytes` is not a valid ID.
+    pub fn from_key_bytes(bytes: &[u8]) -> Result<Self, TinyIdError> {
+        let data: [u8; 8] = bytes.try_into().map_err(|_| TinyIdError::InvalidLength)?;
+        Self::from_bytes(data)
+    }
+
+    /// Get the `(start, end)` key bounds covering every [`TinyId`] that starts with `prefix`,
+    /// where `start` is inclusive and `end` is exclusive. Suitable for a `RocksDB` iterator's lower
+    /// and upper bounds, or `sled::Tree::range(start..end)`.
+    ///
+    /// Both bounds are fixed-size arrays, so no allocation is needed. `end` is not necessarily a
+    /// valid ID, only a byte string greater than every key with the prefix.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `prefix` is longer than 8 bytes.
+    /// - [`TinyIdError::InvalidCharacters`] if `prefix` contains invalid characters.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let (start, end) = TinyId::prefix_range_bounds("ab").unwrap();
+    /// assert_eq!(&start, b"ab------");
+    /// assert_eq!(&end, b"ac------");
+    ///
+    /// let id = TinyId::from_str_unchecked("abzzzzzz");
+    /// assert!((start..end).contains(id.as_key_bytes()));
+    /// ```
+    pub fn prefix_range_bounds(prefix: &str) -> Result<([u8; 8], [u8; 8]), TinyIdError> {
+        let prefix = prefix.as_bytes();
+        if prefix.len() > 8 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        if !prefix.iter().all(|&b| Self::is_valid_byte(b)) {
+            return Err(TinyIdError::InvalidCharacters);
+        }
+        let mut start = [FIRST_LETTER; 8];
+        start[..prefix.len()].copy_from_slice(prefix);
+        let mut end = start;
+        match prefix.len().checked_sub(1) {
+            Some(last) => end[last] += 1,
+            None => end[0] = PAST_LAST_LETTER,
+        }
+        Ok((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn key_bytes() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert_eq!(id.as_key_bytes(), &id.sort_key());
+            assert_eq!(TinyId::from_key_bytes(id.as_key_bytes()), Ok(id));
+        }
+        assert_eq!(
+            TinyId::from_key_bytes(b"abc"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_key_bytes(b"abc!efgh"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::from_key_bytes(&TinyId::NULL_DATA),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn prefix_bounds() {
+        assert_eq!(
+            TinyId::prefix_range_bounds(""),
+            Ok((*b"--------", *b"{-------"))
+        );
+        assert_eq!(
+            TinyId::prefix_range_bounds("az"),
+            Ok((*b"az------", *b"a{------"))
+        );
+        assert_eq!(
+            TinyId::prefix_range_bounds("abcdefgh"),
+            Ok((*b"abcdefgh", *b"abcdefgi"))
+        );
+        assert_eq!(
+            TinyId::prefix_range_bounds("abcdefghi"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::prefix_range_bounds("a!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn prefix_bounds_match_starts_with() {
+        for prefix in ["", "a", "Z", "-", "_", "z", "9", "ab", "zz", "a-_"] {
+            let (start, end) = TinyId::prefix_range_bounds(prefix).unwrap();
+            for _ in 0..1000 {
+                let id = TinyId::random();
+                assert_eq!(
+                    (start..end).contains(id.as_key_bytes()),
+                    id.starts_with(prefix),
+                    "{prefix} {id}"
+                );
+            }
+            for edge in [start, end] {
+                if let Ok(id) = TinyId::from_bytes(edge) {
+                    assert_eq!(
+                        (start..end).contains(id.as_key_bytes()),
+                        id.starts_with(prefix)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -38,8 +38,138 @@
     clippy::cargo_common_metadata
 )]
 
+pub mod analysis;
+pub mod collections;
+pub mod fmt;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(feature = "token")]
+pub mod token;
+
+mod alphabet;
+mod atomic;
+mod block;
+mod bloom;
+mod collect;
+mod color;
+mod default_random;
+mod derive;
+mod emoji;
+mod encoder;
+mod feistel;
+mod gen_id;
+mod generator;
+mod hash;
+mod hooks;
+#[cfg(feature = "identicon")]
+mod identicon;
+mod index;
+mod integrations;
+mod key;
+mod mnemonic;
+mod nanoid;
+mod ordering;
+mod pair;
+mod path;
+mod pronounceable;
+mod proquint;
+mod provider;
+#[cfg(feature = "qr")]
+mod qr;
+mod range;
+mod rate_limited;
+#[cfg(feature = "concurrent")]
+mod registry;
+mod rng;
+#[cfg(feature = "tokio")]
+mod service;
+mod shared;
+mod spell;
+mod static_registry;
+mod stats;
+mod str_buf;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "serde")]
+mod string_id;
+mod suggest;
+mod time_sorted;
+mod unique;
+mod validation;
+mod wire;
+
+pub use alphabet::Alphabet;
+pub use atomic::AtomicTinyId;
+pub use block::{Block, BlockAllocator};
+pub use bloom::BloomUniqueGenerator;
+pub use default_random::DefaultRandom;
+pub use encoder::Encoder;
+pub use feistel::FeistelGenerator;
+pub use gen_id::{GenId, GenIdPool};
+pub use generator::Generator;
+#[cfg(feature = "identicon")]
+pub use identicon::Identicon;
+#[cfg(feature = "warp")]
+pub use integrations::warp::{recover_invalid_tinyid, tinyid_path, InvalidTinyId};
+pub use ordering::NullsLast;
+pub use pair::TinyIdPair;
+pub use path::IdPath;
+pub use pronounceable::Pronounceable;
+pub use provider::{FixedProvider, IdProvider, RandomProvider, SequentialProvider};
+pub use range::{TinyIdRange, TinyIdRangeIter};
+pub use rate_limited::RateLimitedGenerator;
+#[cfg(feature = "concurrent")]
+pub use registry::ConcurrentIdRegistry;
+pub use rng::{entropy_from, reseed_thread};
+#[cfg(feature = "test-util")]
+pub use rng::{env_seed, SEED_VAR};
+#[cfg(feature = "tokio")]
+pub use service::{IdHandle, IdService};
+pub use shared::SharedGenerator;
+pub use static_registry::StaticIdRegistry;
+pub use stats::GenerationStats;
+pub use str_buf::TinyIdStr;
+#[cfg(feature = "futures")]
+pub use stream::{id_stream, IdStream};
+#[cfg(feature = "serde")]
+pub use string_id::StringTinyId;
+pub use time_sorted::TimeSortedGenerator;
+pub use unique::UniqueGenerator;
+pub use validation::Validation;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+/// Create a [`TinyId`] from a string literal, checked at compile time, so literal IDs in tests
+/// and fixtures need no runtime parsing or `unwrap`. Expands to a constant, so it can also be
+/// used to initialize `const` and `static` items.
+///
+/// ## Example
+/// ```
+/// use tinyid::{tinyid, TinyId};
+///
+/// const FIXTURE: TinyId = tinyid!("user0001");
+/// assert_eq!(FIXTURE, "user0001".parse::<TinyId>().unwrap());
+/// ```
+///
+/// Literals of the wrong length, or with invalid characters, fail to compile:
+/// ```compile_fail
+/// let id = tinyid::tinyid!("user001");
+/// ```
+/// ```compile_fail
+/// let id = tinyid::tinyid!("user 001");
+/// ```
+#[macro_export]
+macro_rules! tinyid {
+    ($id:literal) => {{
+        const ID: $crate::TinyId = $crate::TinyId::from_str_const($id);
+        ID
+    }};
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
 /// Error type used by [`TinyId`] operations that are fallible.
 pub enum TinyIdError {
     /// Error returned when a string has too many characters to be a valid [`TinyId`].
@@ -75,7 +205,7 @@ impl std::error::Error for TinyIdError {}
 /// for tasks that don't require the utmost security or uniqueness. During lightweight testing,
 /// between 1 and 10 million IDs can be generated without any collisions, and performance has
 /// been pretty good.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TinyId {
     data: [u8; 8],
@@ -154,6 +284,27 @@ impl TinyId {
         Self::random_fastrand2()
     }
 
+    /// Attempt to create a new random [`TinyId`], reporting a failure of the entropy source
+    /// instead of panicking.
+    ///
+    /// With the `getrandom` feature enabled, this draws from the operating system's secure random
+    /// number generator, which can fail (e.g. on some embedded targets or before the system has
+    /// gathered enough entropy). Otherwise, or when the `test-util` feature's `TINYID_SEED` makes
+    /// generation deterministic, this uses the same generator as [`TinyId::random`] and never
+    /// fails.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the entropy source fails.
+    pub fn try_random() -> Result<Self, TinyIdError> {
+        #[cfg(feature = "getrandom")]
+        if !rng::is_deterministic() {
+            let mut bytes = [0u8; 8];
+            getrandom::getrandom(&mut bytes).map_err(|_| TinyIdError::GenerationFailure)?;
+            return Ok(Self::from_random_bits(u64::from_be_bytes(bytes)));
+        }
+        Ok(Self::random())
+    }
+
     /// Checks whether this [`TinyId`] is null or has any invalid bytes.
     #[must_use]
     pub fn is_valid(self) -> bool {
@@ -201,6 +352,40 @@ impl TinyId {
         Self { data }
     }
 
+    /// Convert from [`&str`] to [`TinyId`] in a `const` context, validating the length and
+    /// characters of the input. This is what [`tinyid!`] uses to check literal IDs at compile
+    /// time; prefer [`str::parse`] at runtime.
+    ///
+    /// ## Panics
+    /// If `s` is not exactly 8 valid characters. In a `const` item this is a compile error.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// const ADMIN: TinyId = TinyId::from_str_const("admin___");
+    /// assert_eq!(ADMIN.to_string(), "admin___");
+    /// ```
+    #[must_use]
+    pub const fn from_str_const(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(
+            bytes.len() == 8,
+            "a TinyId must be exactly 8 characters long"
+        );
+        let mut data = Self::NULL_DATA;
+        let mut i = 0;
+        while i < 8 {
+            assert!(
+                Self::is_valid_byte(bytes[i]),
+                "a TinyId may only contain A-Z, a-z, 0-9, '_' and '-'"
+            );
+            data[i] = bytes[i];
+            i += 1;
+        }
+        Self { data }
+    }
+
     /// Convert this [`TinyId`] to an array of 8 bytes.
     #[must_use]
     pub fn to_bytes(self) -> [u8; 8] {
@@ -279,6 +464,104 @@ impl TinyId {
         }
     }
 
+    /// Checks whether this [`TinyId`] is equal to `other`, ignoring ASCII case. Useful when users
+    /// retype IDs with the wrong case. Note that IDs differing only in case are distinct IDs.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let a = TinyId::from_str_unchecked("aBcD-_12");
+    /// let b = TinyId::from_str_unchecked("AbCd-_12");
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_ignore_case(&b));
+    /// ```
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &TinyId) -> bool {
+        self.data.eq_ignore_ascii_case(&other.data)
+    }
+
+    /// Checks whether this [`TinyId`] is equal to the given string, ignoring ASCII case. Unlike
+    /// [`TinyId::starts_with`] this does not allocate.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("aBcD-_12");
+    /// assert!(id.eq_ignore_case_str("ABCD-_12"));
+    /// assert!(!id.eq_ignore_case_str("ABCD-_1"));
+    /// ```
+    #[must_use]
+    pub fn eq_ignore_case_str(&self, other: &str) -> bool {
+        self.data.eq_ignore_ascii_case(other.as_bytes())
+    }
+
+    /// Checks whether this [`TinyId`] matches the given glob `pattern`, where `?` matches any single
+    /// character and `*` matches any run of characters (including none). All other characters must
+    /// match exactly. Does not allocate.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert!(id.matches_glob("ab?d*"));
+    /// assert!(id.matches_glob("*gh"));
+    /// assert!(!id.matches_glob("ab?d"));
+    /// ```
+    #[must_use]
+    pub fn matches_glob(&self, pattern: &str) -> bool {
+        let pattern = pattern.as_bytes();
+        let (mut p, mut d) = (0, 0);
+        // Position of the last `*` seen in the pattern, and the data position it was tried at.
+        let mut backtrack: Option<(usize, usize)> = None;
+        while d < self.data.len() {
+            match pattern.get(p) {
+                Some(b'*') => {
+                    backtrack = Some((p, d));
+                    p += 1;
+                }
+                Some(&ch) if ch == b'?' || ch == self.data[d] => {
+                    p += 1;
+                    d += 1;
+                }
+                _ => match backtrack {
+                    Some((star, start)) => {
+                        backtrack = Some((star, start + 1));
+                        p = star + 1;
+                        d = start + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|&ch| ch == b'*')
+    }
+
+    /// Apply `f` to each `(position, byte)` of this [`TinyId`] and validate the result, for
+    /// custom canonicalization such as case folding or mapping ambiguous letters.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if `f` produces a byte which is not a valid letter.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("aBcD-_12");
+    /// let lower = id.map_bytes(|_, b| b.to_ascii_lowercase()).unwrap();
+    /// assert_eq!(lower, "abcd-_12");
+    /// assert!(id.map_bytes(|i, b| if i == 0 { b'!' } else { b }).is_err());
+    /// ```
+    pub fn map_bytes(self, f: impl Fn(usize, u8) -> u8) -> Result<Self, TinyIdError> {
+        let mut data = self.data;
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = f(i, *b);
+        }
+        Self::from_bytes(data)
+    }
+
     /// Create a new random [`TinyId`].
     ///
     /// This method calls [`fastrand::u8`] 8 times. Twice as fast as [`TinyId::random_fastrand2`].
@@ -289,7 +572,7 @@ impl TinyId {
         const LETTER_COUNT_U8: u8 = TinyId::LETTER_COUNT as u8;
         let mut data = Self::NULL_DATA;
         for ch in &mut data {
-            *ch = Self::LETTERS[fastrand::u8(0..LETTER_COUNT_U8) as usize];
+            *ch = Self::LETTERS[rng::u8_below(LETTER_COUNT_U8) as usize];
         }
         Self { data }
     }
@@ -300,8 +583,14 @@ impl TinyId {
     /// them to index the letter array.
     #[must_use]
     pub(crate) fn random_fastrand2() -> Self {
-        let seed = fastrand::u64(..);
-        let mut data: [u8; 8] = seed.to_be_bytes();
+        Self::from_random_bits(rng::u64())
+    }
+
+    /// Create a new [`TinyId`] from 64 bits of randomness, splitting it into bytes and using
+    /// them to index the letter array.
+    #[must_use]
+    pub(crate) fn from_random_bits(bits: u64) -> Self {
+        let mut data: [u8; 8] = bits.to_be_bytes();
         for b in &mut data {
             *b = Self::LETTERS[*b as usize % Self::LETTER_COUNT];
         }
@@ -347,6 +636,22 @@ impl<'a> TryFrom<&'a [u8]> for TinyId {
     }
 }
 
+impl TryFrom<Vec<u8>> for TinyId {
+    type Error = TinyIdError;
+
+    fn try_from(value: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Self::try_from(value.as_slice())
+    }
+}
+
+impl TryFrom<String> for TinyId {
+    type Error = TinyIdError;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
 impl TryFrom<u64> for TinyId {
     type Error = TinyIdError;
 
@@ -372,6 +677,26 @@ impl std::fmt::Display for TinyId {
     }
 }
 
+/// Shows both the string form and the [`TinyId::to_u64`] value, e.g.
+/// `TinyId("abcd1234" / 7017280451369906996)`. The positions of any invalid bytes are listed after
+/// the value, and the null ID is shown as `TinyId(null)`.
+impl std::fmt::Debug for TinyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_null() {
+            return f.write_str("TinyId(null)");
+        }
+        let text: String = self.data.iter().map(|&b| b as char).collect();
+        write!(f, "TinyId({text:?} / {}", self.to_u64())?;
+        let invalid: Vec<usize> = (0..self.data.len())
+            .filter(|&i| !Self::is_valid_byte(self.data[i]))
+            .collect();
+        if !invalid.is_empty() {
+            write!(f, ", invalid bytes at {invalid:?}")?;
+        }
+        f.write_str(")")
+    }
+}
+
 impl Default for TinyId {
     fn default() -> Self {
         Self::null()
@@ -443,6 +768,49 @@ impl PartialEq<TinyId> for &TinyId {
         self.data == other.data
     }
 }
+impl PartialEq<str> for TinyId {
+    fn eq(&self, other: &str) -> bool {
+        self.data == other.as_bytes()
+    }
+}
+impl PartialEq<&str> for TinyId {
+    fn eq(&self, other: &&str) -> bool {
+        self.data == other.as_bytes()
+    }
+}
+impl PartialEq<TinyId> for str {
+    fn eq(&self, other: &TinyId) -> bool {
+        self.as_bytes() == other.data
+    }
+}
+impl PartialEq<TinyId> for &str {
+    fn eq(&self, other: &TinyId) -> bool {
+        self.as_bytes() == other.data
+    }
+}
+
+/// Compares against the display form of the [`TinyId`], byte-wise like [`str`]'s own ordering,
+/// so a [`TinyId`] can be used to probe sorted string keys.
+impl PartialOrd<str> for TinyId {
+    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
+        Some(self.data.as_slice().cmp(other.as_bytes()))
+    }
+}
+impl PartialOrd<&str> for TinyId {
+    fn partial_cmp(&self, other: &&str) -> Option<std::cmp::Ordering> {
+        Some(self.data.as_slice().cmp(other.as_bytes()))
+    }
+}
+impl PartialOrd<TinyId> for str {
+    fn partial_cmp(&self, other: &TinyId) -> Option<std::cmp::Ordering> {
+        Some(self.as_bytes().cmp(other.data.as_slice()))
+    }
+}
+impl PartialOrd<TinyId> for &str {
+    fn partial_cmp(&self, other: &TinyId) -> Option<std::cmp::Ordering> {
+        Some(self.as_bytes().cmp(other.data.as_slice()))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -466,6 +834,25 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn debug() {
+        let id = TinyId::from_str_unchecked("abcd1234");
+        assert_eq!(
+            format!("{id:?}"),
+            format!("TinyId(\"abcd1234\" / {})", id.to_u64())
+        );
+        assert_eq!(format!("{:?}", TinyId::null()), "TinyId(null)");
+        let id = TinyId::from_bytes_unchecked(*b"ab\0d\x07fgh");
+        assert_eq!(
+            format!("{id:?}"),
+            format!(
+                "TinyId(\"ab\\0d\\u{{7}}fgh\" / {}, invalid bytes at [2, 4])",
+                id.to_u64()
+            )
+        );
+    }
+
     #[test]
     #[cfg_attr(coverage, no_coverage)]
     fn letters() {
@@ -480,6 +867,34 @@ mod tests {
         assert!(!TinyId::is_valid_byte(TinyId::NULL_CHAR));
     }
 
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn const_literals() {
+        const ID: TinyId = tinyid!("A1b2_-zZ");
+        static IDS: [TinyId; 2] = [tinyid!("--------"), tinyid!("zzzzzzzz")];
+        assert_eq!(ID, TinyId::from_str("A1b2_-zZ").unwrap());
+        assert_eq!(IDS[0].to_index(), Some(0));
+        assert!(IDS.iter().all(|id| id.is_valid()));
+        for letter in TinyId::LETTERS {
+            let s = String::from_utf8(vec![letter; 8]).unwrap();
+            assert_eq!(TinyId::from_str_const(&s), TinyId::from_str_unchecked(&s));
+        }
+    }
+
+    #[test]
+    #[should_panic = "exactly 8 characters"]
+    #[cfg_attr(coverage, no_coverage)]
+    fn const_wrong_length() {
+        let _ = TinyId::from_str_const("abcdefghi");
+    }
+
+    #[test]
+    #[should_panic = "may only contain"]
+    #[cfg_attr(coverage, no_coverage)]
+    fn const_invalid_characters() {
+        let _ = TinyId::from_str_const("abcdefg!");
+    }
+
     #[test]
     #[cfg_attr(coverage, no_coverage)]
     fn basic_usage() {
@@ -552,20 +967,20 @@ mod tests {
         let result = TinyId::try_from([b'!', b'b', b'c', b'd', b'e', b'f', b'g', b'h']);
         assert!(result.is_err());
 
-        let result = TinyId::try_from(&[b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h']);
+        let result = TinyId::try_from(b"abcdefgh");
         assert!(result.is_ok());
         let id = result.unwrap();
         assert_eq!(id.to_string(), "abcdefgh");
-        let result = TinyId::try_from(&[b'!', b'b', b'c', b'd', b'e', b'f', b'g', b'h']);
+        let result = TinyId::try_from(b"!bcdefgh");
         assert!(result.is_err());
 
-        let result = TinyId::try_from(&[b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as &[u8]);
+        let result = TinyId::try_from(b"abcdefgh" as &[u8]);
         assert!(result.is_ok());
         let id = result.unwrap();
         assert_eq!(id.to_string(), "abcdefgh");
-        let result = TinyId::try_from(&[b'!', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as &[u8]);
+        let result = TinyId::try_from(b"!bcdefgh" as &[u8]);
         assert!(result.is_err());
-        let result = TinyId::try_from(&[b'!', b'b', b'c', b'd', b'e', b'f', b'g'] as &[u8]);
+        let result = TinyId::try_from(b"!bcdefg" as &[u8]);
         assert!(result.is_err());
     }
 
@@ -581,7 +996,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "index out of bounds")]
     #[cfg_attr(coverage, no_coverage)]
     fn bad_froms_panic1() {
         let _id = TinyId::from_str_unchecked("oopsie poopsie!");
@@ -609,10 +1024,10 @@ mod tests {
         assert!(id2 == &id.data.to_vec());
         assert!(id3 == id.data);
         assert!(id3 == [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as [u8; 8]);
-        assert!(id == &[b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as &[u8; 8]);
-        assert!(id2 == &[b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as &[u8]);
+        assert!(id == b"abcdefgh" as &[u8; 8]);
+        assert!(id2 == b"abcdefgh" as &[u8]);
         assert!(&id3 == [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as [u8; 8]);
-        assert!(&id == &[b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'] as &[u8; 8]);
+        assert!(&id == b"abcdefgh" as &[u8; 8]);
         let bytes: [u8; 8] = [b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h'];
         assert!(id == bytes);
 
@@ -622,4 +1037,136 @@ mod tests {
         assert!(id.data == TinyId::NULL_DATA);
         assert!(id == TinyId::default());
     }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn eq_ignore_case() {
+        let a = TinyId::from_str_unchecked("aBcD-_12");
+        let b = TinyId::from_str_unchecked("AbCd-_12");
+        let c = TinyId::from_str_unchecked("AbCd-_13");
+        assert!(a.eq_ignore_case(&a));
+        assert!(a.eq_ignore_case(&b));
+        assert!(!a.eq_ignore_case(&c));
+        assert!(a.eq_ignore_case_str("abcd-_12"));
+        assert!(!a.eq_ignore_case_str("abcd_-12"));
+        assert!(!a.eq_ignore_case_str(""));
+        assert!(!a.eq_ignore_case_str("abcd-_123"));
+        assert!(TinyId::null().eq_ignore_case(&TinyId::null()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn matches_glob() {
+        let id = TinyId::from_str_unchecked("ab-d_fgh");
+        assert!(id.matches_glob("ab-d_fgh"));
+        assert!(id.matches_glob("*"));
+        assert!(id.matches_glob("********"));
+        assert!(id.matches_glob("????????"));
+        assert!(id.matches_glob("a*"));
+        assert!(id.matches_glob("*h"));
+        assert!(id.matches_glob("a*d*h"));
+        assert!(id.matches_glob("?b-*?"));
+        assert!(id.matches_glob("*-d_*"));
+        assert!(!id.matches_glob("ab-d_fg"));
+        assert!(!id.matches_glob(""));
+        assert!(!id.matches_glob("???????"));
+        assert!(!id.matches_glob("?????????"));
+        assert!(!id.matches_glob("ab-d_f?"));
+        assert!(!id.matches_glob("*x*"));
+        assert!(!id.matches_glob("AB*"));
+        assert!(!id.matches_glob("é*"));
+
+        let id = TinyId::from_str_unchecked("aaaaaaab");
+        assert!(id.matches_glob("*a*ab"));
+        assert!(!id.matches_glob("*a*aa"));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn try_random() {
+        let ids = (0..1000)
+            .map(|_| TinyId::try_random().unwrap())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(ids.len(), 1000);
+        assert!(ids.iter().all(|id| id.is_valid()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn compare_with_str() {
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        assert!(id == "abcdefgh");
+        assert!("abcdefgh" == id);
+        assert!(id == *"abcdefgh");
+        assert!(id != "abcdefg");
+        assert!(id < "abcdefgi");
+        assert!(id > "abcdefg");
+        assert!(id > "ABCDEFGH");
+        assert!("abcdefgi" > id);
+        assert!(*"abc" < id);
+        assert_eq!(id.partial_cmp("abcdefgh"), Some(std::cmp::Ordering::Equal));
+
+        let mut keys = (0..1000)
+            .map(|_| TinyId::random().to_string())
+            .collect::<Vec<_>>();
+        keys.sort();
+        for (index, key) in keys.iter().enumerate() {
+            let probe = TinyId::from_str_unchecked(key);
+            let found = keys.binary_search_by(|k| probe.partial_cmp(k.as_str()).unwrap().reverse());
+            assert_eq!(found, Ok(index));
+        }
+        for _ in 0..1000 {
+            let a = TinyId::random();
+            let b = TinyId::random();
+            assert_eq!(a.partial_cmp(b.to_string().as_str()), Some(a.cmp(&b)));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn try_from_owned() {
+        let id = TinyId::random();
+        assert_eq!(TinyId::try_from(id.to_string()), Ok(id));
+        assert_eq!(TinyId::try_from(id.to_bytes().to_vec()), Ok(id));
+        assert_eq!(
+            TinyId::try_from(String::from("abc")),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::try_from(String::from("abc!efgh")),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert!(matches!(
+            TinyId::try_from(b"abc".to_vec()),
+            Err(TinyIdError::Conversion(_))
+        ));
+        assert_eq!(
+            TinyId::try_from(b"abc!efgh".to_vec()),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn map_bytes() {
+        let id = TinyId::from_str_unchecked("O0lI1-Ab");
+        let unambiguous = id.map_bytes(|_, b| match b {
+            b'O' => b'0',
+            b'l' | b'I' => b'1',
+            _ => b,
+        });
+        assert_eq!(unambiguous, Ok(TinyId::from_str_unchecked("00111-Ab")));
+        assert_eq!(
+            id.map_bytes(|i, b| if i == 7 { b'_' } else { b }),
+            Ok(TinyId::from_str_unchecked("O0lI1-A_"))
+        );
+        assert_eq!(
+            id.map_bytes(|_, _| b' '),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::null().map_bytes(|_, b| b),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
 }
@@ -0,0 +1,138 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{Pronounceable, TinyId, TinyIdError};
+
+/// The vowels used in mnemonic words. `e` is left out so that there are exactly four (2 bits).
+const VOWELS: [u8; 4] = *b"aiou";
+/// The number of words in a mnemonic.
+const WORD_COUNT: u32 = 4;
+/// The number of bits encoded by each mnemonic word.
+const WORD_BITS: u32 = 12;
+
+/// Get mnemonic word number `n` (`0..4096`).
+#[allow(clippy::cast_possible_truncation)]
+fn word(n: u64) -> [u8; 4] {
+    [
+        Pronounceable::CONSONANTS[(n >> 8 & 0xF) as usize],
+        VOWELS[(n >> 6 & 0x3) as usize],
+        Pronounceable::CONSONANTS[(n >> 2 & 0xF) as usize],
+        VOWELS[(n & 0x3) as usize],
+    ]
+}
+
+/// Get the number of the given mnemonic word (case-insensitive), if it is one.
+fn word_number(word: &str) -> Option<u64> {
+    let position = |pool: &[u8], b: u8| {
+        pool.iter()
+            .position(|&p| p == b.to_ascii_lowercase())
+            .map(|i| i as u64)
+    };
+    match *word.as_bytes() {
+        [c1, v1, c2, v2] => Some(
+            position(&Pronounceable::CONSONANTS, c1)? << 8
+                | position(&VOWELS, v1)? << 6
+                | position(&Pronounceable::CONSONANTS, c2)? << 2
+                | position(&VOWELS, v2)?,
+        ),
+        _ => None,
+    }
+}
+
+impl TinyId {
+    /// Convert this [`TinyId`] to four short words which are easier to exchange verbally than
+    /// individual (case-sensitive) characters, e.g. `"bado-kumi-tasu-liro"`. Returns `None` if
+    /// this ID is not valid.
+    ///
+    /// Each word comes from a fixed list of 4096 pronounceable consonant-vowel-consonant-vowel
+    /// words, so each one encodes 12 of the ID's 48 bits. [`TinyId::from_mnemonic`] converts
+    /// back.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let words = id.to_mnemonic().unwrap();
+    /// assert_eq!(words.split('-').count(), 4);
+    /// assert_eq!(TinyId::from_mnemonic(&words), Ok(id));
+    /// ```
+    #[must_use]
+    pub fn to_mnemonic(self) -> Option<String> {
+        let index = self.to_index()?;
+        let words: Vec<String> = (0..WORD_COUNT)
+            .rev()
+            .map(|i| word(index >> (i * WORD_BITS) & 0xFFF))
+            .map(|w| w.iter().map(|&b| b as char).collect())
+            .collect();
+        Some(words.join("-"))
+    }
+
+    /// Convert four mnemonic words created by [`TinyId::to_mnemonic`] back to a [`TinyId`]. The
+    /// words may be separated by dashes and/or whitespace, and are case-insensitive.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there are not exactly four words.
+    /// - [`TinyIdError::InvalidCharacters`] if any of the words are not mnemonic words.
+    pub fn from_mnemonic(words: &str) -> Result<Self, TinyIdError> {
+        let words: Vec<&str> = words
+            .split(|c: char| c == '-' || c.is_whitespace())
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.len() != WORD_COUNT as usize {
+            return Err(TinyIdError::InvalidLength);
+        }
+        let index = words.iter().try_fold(0u64, |acc, w| {
+            word_number(w)
+                .map(|n| acc << WORD_BITS | n)
+                .ok_or(TinyIdError::InvalidCharacters)
+        })?;
+        Ok(Self::from_index_wrapping(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn words() {
+        use std::collections::HashSet;
+        let all: HashSet<[u8; 4]> = (0..4096).map(word).collect();
+        assert_eq!(all.len(), 4096);
+        for n in 0..4096 {
+            let w = word(n);
+            assert_eq!(word_number(std::str::from_utf8(&w).unwrap()), Some(n));
+        }
+        assert_eq!(word_number("bae"), None);
+        assert_eq!(word_number("baba!"), None);
+        assert_eq!(word_number("beba"), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn roundtrip() {
+        for _ in 0..1_000 {
+            let id = TinyId::random();
+            let words = id.to_mnemonic().unwrap();
+            assert_eq!(TinyId::from_mnemonic(&words), Ok(id));
+            let spaced = words.replace('-', " ").to_uppercase();
+            assert_eq!(TinyId::from_mnemonic(&spaced), Ok(id));
+        }
+        let first = TinyId::from_index_wrapping(0);
+        assert_eq!(first.to_mnemonic().unwrap(), "baba-baba-baba-baba");
+        assert_eq!(TinyId::null().to_mnemonic(), None);
+        assert_eq!(
+            TinyId::from_mnemonic("baba baba baba"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_mnemonic("baba baba baba bebe"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
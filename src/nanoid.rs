@@ -0,0 +1,175 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Interop with IDs generated by the [`nanoid`](https://crates.io/crates/nanoid) crate.
+//!
+//! nanoid's default alphabet is made of the same 64 URL-safe characters as
+//! [`TinyId::LETTERS`], so an 8 character nanoid is already a valid [`TinyId`], and the
+//! display form of a [`TinyId`] is a valid nanoid.
+
+use crate::{hash::hash_bytes, TinyId, TinyIdError};
+
+/// Check that `s` is a non-empty nanoid using the default alphabet.
+fn validate(s: &str) -> Result<(), TinyIdError> {
+    if s.is_empty() {
+        return Err(TinyIdError::InvalidLength);
+    }
+    if s.bytes().all(TinyId::is_valid_byte) {
+        Ok(())
+    } else {
+        Err(TinyIdError::InvalidCharacters)
+    }
+}
+
+impl TinyId {
+    /// Parse an 8 character nanoid (using the default alphabet) as a [`TinyId`]. The ID is
+    /// identical to the nanoid, so it converts back with [`TinyId::to_nanoid`].
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `s` is not exactly 8 characters long. See
+    ///   [`TinyId::from_nanoid_truncated`] and [`TinyId::from_nanoid_hashed`] for longer nanoids.
+    /// - [`TinyIdError::InvalidCharacters`] if `s` contains characters outside of nanoid's
+    ///   default alphabet.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_nanoid("V1StGXR_").unwrap();
+    /// assert_eq!(id.to_nanoid(), "V1StGXR_");
+    /// ```
+    pub fn from_nanoid(s: &str) -> Result<Self, TinyIdError> {
+        validate(s)?;
+        s.parse()
+    }
+
+    /// Create a [`TinyId`] from the first 8 characters of a nanoid (using the default alphabet)
+    /// of 8 or more characters. Note that distinct nanoids sharing a prefix truncate to the same
+    /// ID, see [`TinyId::from_nanoid_hashed`] for an alternative that uses the whole nanoid.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `s` is shorter than 8 characters.
+    /// - [`TinyIdError::InvalidCharacters`] if `s` contains characters outside of nanoid's
+    ///   default alphabet.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_nanoid_truncated("V1StGXR8_Z5jdHi6B-myT").unwrap();
+    /// assert_eq!(id.to_string(), "V1StGXR8");
+    /// ```
+    pub fn from_nanoid_truncated(s: &str) -> Result<Self, TinyIdError> {
+        validate(s)?;
+        s.get(..8).ok_or(TinyIdError::InvalidLength)?.parse()
+    }
+
+    /// Create a [`TinyId`] from a nanoid (using the default alphabet) of any length by hashing
+    /// it. The same nanoid always hashes to the same ID, across processes and releases.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `s` is empty.
+    /// - [`TinyIdError::InvalidCharacters`] if `s` contains characters outside of nanoid's
+    ///   default alphabet.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let a = TinyId::from_nanoid_hashed("V1StGXR8_Z5jdHi6B-myT").unwrap();
+    /// let b = TinyId::from_nanoid_hashed("V1StGXR8_Z5jdHi6B-myT").unwrap();
+    /// assert_eq!(a, b);
+    /// assert!(a.is_valid());
+    /// ```
+    pub fn from_nanoid_hashed(s: &str) -> Result<Self, TinyIdError> {
+        validate(s)?;
+        Ok(Self::from_index_wrapping(hash_bytes(s.as_bytes())))
+    }
+
+    /// Convert this [`TinyId`] to a nanoid. This is the same as its display form.
+    #[must_use]
+    pub fn to_nanoid(self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NANOID_ALPHABET: &[u8; 64] =
+        b"_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn same_alphabet() {
+        let mut ours = TinyId::LETTERS;
+        let mut theirs = *NANOID_ALPHABET;
+        ours.sort_unstable();
+        theirs.sort_unstable();
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn exact() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            assert_eq!(TinyId::from_nanoid(&id.to_nanoid()), Ok(id));
+        }
+        assert_eq!(TinyId::from_nanoid(""), Err(TinyIdError::InvalidLength));
+        assert_eq!(TinyId::from_nanoid("abc"), Err(TinyIdError::InvalidLength));
+        assert_eq!(
+            TinyId::from_nanoid("abcdefghi"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_nanoid("abcdefg!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn truncated() {
+        assert_eq!(
+            TinyId::from_nanoid_truncated("abcdefgh"),
+            Ok(TinyId::from_str_unchecked("abcdefgh"))
+        );
+        assert_eq!(
+            TinyId::from_nanoid_truncated("abcdefgh-_1234"),
+            Ok(TinyId::from_str_unchecked("abcdefgh"))
+        );
+        assert_eq!(
+            TinyId::from_nanoid_truncated("abcdefg"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_nanoid_truncated("abcdefghi!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn hashed() {
+        let a = TinyId::from_nanoid_hashed("abcdefgh-_1234").unwrap();
+        let b = TinyId::from_nanoid_hashed("abcdefgh-_1235").unwrap();
+        assert!(a.is_valid());
+        assert!(b.is_valid());
+        assert_ne!(a, b);
+        assert_eq!(TinyId::from_nanoid_hashed("abcdefgh-_1234"), Ok(a));
+        assert!(TinyId::from_nanoid_hashed("a").unwrap().is_valid());
+        assert_eq!(
+            TinyId::from_nanoid_hashed(""),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_nanoid_hashed("abc def"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
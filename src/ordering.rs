@@ -0,0 +1,100 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::cmp::Ordering;
+
+use crate::TinyId;
+
+/// A [`TinyId`] wrapper that orders the null ID after every other ID, instead of before. Useful
+/// for sorted views where the null ID stands for an absent value.
+///
+/// ## Example
+/// ```
+/// use tinyid::{NullsLast, TinyId};
+///
+/// let mut ids = vec![TinyId::random(), TinyId::null(), TinyId::random()];
+/// ids.sort_by_key(|&id| NullsLast(id));
+/// assert!(ids[2].is_null());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct NullsLast(pub TinyId);
+
+impl PartialOrd for NullsLast {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NullsLast {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_nulls_last(&other.0)
+    }
+}
+
+impl From<TinyId> for NullsLast {
+    fn from(id: TinyId) -> Self {
+        Self(id)
+    }
+}
+
+impl From<NullsLast> for TinyId {
+    fn from(wrapper: NullsLast) -> Self {
+        wrapper.0
+    }
+}
+
+impl TinyId {
+    /// Compare two [`TinyId`]s like [`Ord::cmp`], except that the null ID is ordered after every
+    /// other ID. Can be passed to [`slice::sort_by`]. See also [`NullsLast`].
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let mut ids = vec![TinyId::null(), TinyId::random()];
+    /// ids.sort_by(TinyId::cmp_nulls_last);
+    /// assert!(ids[1].is_null());
+    /// ```
+    #[must_use]
+    pub fn cmp_nulls_last(&self, other: &TinyId) -> Ordering {
+        match (self.is_null(), other.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.cmp(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn nulls_last() {
+        let mut ids = (0..100).map(|_| TinyId::random()).collect::<Vec<_>>();
+        ids.extend([TinyId::null(), TinyId::null()]);
+        fastrand::shuffle(&mut ids);
+
+        let mut sorted = ids.clone();
+        sorted.sort_by_key(|&id| NullsLast(id));
+        assert!(sorted[100].is_null());
+        assert!(sorted[101].is_null());
+        assert!(sorted[..100].windows(2).all(|w| w[0] <= w[1]));
+
+        let mut by_fn = ids;
+        by_fn.sort_by(TinyId::cmp_nulls_last);
+        assert_eq!(by_fn, sorted);
+
+        let id = TinyId::from_str_unchecked("zzzzzzzz");
+        assert!(NullsLast(id) < NullsLast(TinyId::null()));
+        assert!(NullsLast::from(TinyId::null()) == NullsLast::default());
+        assert_eq!(TinyId::from(NullsLast(id)), id);
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// A composite key made of two [`TinyId`]s, such as `(tenant, entity)`.
+///
+/// Displayed as both IDs separated by a colon (`aaaa1111:bbbb2222`), and ordered by the first ID
+/// and then the second.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TinyIdPair {
+    first: TinyId,
+    second: TinyId,
+}
+
+impl TinyIdPair {
+    /// The character separating the two IDs in the display form of a [`TinyIdPair`].
+    pub const SEPARATOR: char = ':';
+
+    /// Create a new [`TinyIdPair`] from its two parts.
+    #[must_use]
+    pub fn new(first: TinyId, second: TinyId) -> Self {
+        Self { first, second }
+    }
+
+    /// The first [`TinyId`] of this pair.
+    #[must_use]
+    pub fn first(self) -> TinyId {
+        self.first
+    }
+
+    /// The second [`TinyId`] of this pair.
+    #[must_use]
+    pub fn second(self) -> TinyId {
+        self.second
+    }
+
+    /// Split this pair into its two parts.
+    #[must_use]
+    pub fn into_parts(self) -> (TinyId, TinyId) {
+        (self.first, self.second)
+    }
+
+    /// Checks whether both parts of this pair are valid.
+    #[must_use]
+    pub fn is_valid(self) -> bool {
+        self.first.is_valid() && self.second.is_valid()
+    }
+
+    /// Convert this pair to its 16 byte representation, the first ID followed by the second.
+    #[must_use]
+    pub fn to_bytes(self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.first.to_bytes());
+        bytes[8..].copy_from_slice(&self.second.to_bytes());
+        bytes
+    }
+
+    /// Attempt to create a [`TinyIdPair`] from its 16 byte representation.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if either half is not a valid [`TinyId`].
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, TinyIdError> {
+        let (first, second) = bytes.split_at(8);
+        Ok(Self {
+            first: TinyId::try_from(first)?,
+            second: TinyId::try_from(second)?,
+        })
+    }
+}
+
+impl From<(TinyId, TinyId)> for TinyIdPair {
+    fn from((first, second): (TinyId, TinyId)) -> Self {
+        Self::new(first, second)
+    }
+}
+
+impl From<TinyIdPair> for (TinyId, TinyId) {
+    fn from(pair: TinyIdPair) -> Self {
+        pair.into_parts()
+    }
+}
+
+impl std::fmt::Display for TinyIdPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.first, Self::SEPARATOR, self.second)
+    }
+}
+
+impl std::str::FromStr for TinyIdPair {
+    type Err = TinyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s
+            .split_once(Self::SEPARATOR)
+            .ok_or(TinyIdError::InvalidCharacters)?;
+        Ok(Self {
+            first: first.parse()?,
+            second: second.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn display_and_parse() {
+        let a = TinyId::from_str_unchecked("aaaa1111");
+        let b = TinyId::from_str_unchecked("bbbb2222");
+        let pair = TinyIdPair::new(a, b);
+        assert_eq!(pair.to_string(), "aaaa1111:bbbb2222");
+        assert_eq!("aaaa1111:bbbb2222".parse::<TinyIdPair>(), Ok(pair));
+        assert_eq!(pair.first(), a);
+        assert_eq!(pair.second(), b);
+        assert_eq!(<(TinyId, TinyId)>::from(pair), (a, b));
+        assert_eq!(TinyIdPair::from((a, b)), pair);
+        assert!(pair.is_valid());
+        assert!(!TinyIdPair::default().is_valid());
+
+        for bad in ["", "aaaa1111bbbb2222", "aaaa1111:", "aaaa1111:bbbb222!"] {
+            assert!(bad.parse::<TinyIdPair>().is_err(), "{bad}");
+        }
+        assert_eq!(
+            "aaaa1111:bbbb2222:cccc3333".parse::<TinyIdPair>(),
+            Err(TinyIdError::InvalidLength)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn bytes_and_order() {
+        for _ in 0..100 {
+            let pair = TinyIdPair::new(TinyId::random(), TinyId::random());
+            assert_eq!(TinyIdPair::from_bytes(pair.to_bytes()), Ok(pair));
+        }
+        assert!(TinyIdPair::from_bytes([0; 16]).is_err());
+
+        let a = TinyId::from_str_unchecked("aaaaaaaa");
+        let b = TinyId::from_str_unchecked("bbbbbbbb");
+        let mut pairs = vec![
+            TinyIdPair::new(b, a),
+            TinyIdPair::new(a, b),
+            TinyIdPair::new(a, a),
+        ];
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            [
+                TinyIdPair::new(a, a),
+                TinyIdPair::new(a, b),
+                TinyIdPair::new(b, a)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let pair = TinyIdPair::new(TinyId::random(), TinyId::random());
+        let json = serde_json::to_string(&pair).unwrap();
+        assert_eq!(serde_json::from_str::<TinyIdPair>(&json).unwrap(), pair);
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// A chain of [`TinyId`]s addressing a tree-structured resource, displayed as
+/// `root/child/grandchild`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct IdPath {
+    ids: Vec<TinyId>,
+}
+
+impl IdPath {
+    /// The character separating the IDs in the display form of an [`IdPath`].
+    pub const SEPARATOR: char = '/';
+
+    /// Create a new, empty [`IdPath`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`IdPath`] containing only `root`.
+    #[must_use]
+    pub fn root(root: TinyId) -> Self {
+        Self { ids: vec![root] }
+    }
+
+    /// Append `id` to the end of this path.
+    pub fn push(&mut self, id: TinyId) {
+        self.ids.push(id);
+    }
+
+    /// Create a new path by appending `id` to a copy of this one.
+    #[must_use]
+    pub fn join(&self, id: TinyId) -> Self {
+        let mut path = self.clone();
+        path.push(id);
+        path
+    }
+
+    /// Remove and return the last ID of this path, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<TinyId> {
+        self.ids.pop()
+    }
+
+    /// Get the path to the parent of this one, or `None` if this path has fewer than two IDs.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        match self.ids.len() {
+            0 | 1 => None,
+            len => Some(Self {
+                ids: self.ids[..len - 1].to_vec(),
+            }),
+        }
+    }
+
+    /// The first ID of this path, or `None` if it is empty.
+    #[must_use]
+    pub fn first(&self) -> Option<TinyId> {
+        self.ids.first().copied()
+    }
+
+    /// The last ID of this path, or `None` if it is empty.
+    #[must_use]
+    pub fn last(&self) -> Option<TinyId> {
+        self.ids.last().copied()
+    }
+
+    /// The number of IDs in this path.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks whether this path is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The IDs of this path, from the root down.
+    #[must_use]
+    pub fn as_slice(&self) -> &[TinyId] {
+        &self.ids
+    }
+
+    /// Iterate over the IDs of this path, from the root down.
+    pub fn iter(&self) -> impl Iterator<Item = TinyId> + '_ {
+        self.ids.iter().copied()
+    }
+
+    /// Checks whether this path starts with `other`, i.e. whether it is `other` or one of its
+    /// descendants.
+    #[must_use]
+    pub fn starts_with(&self, other: &IdPath) -> bool {
+        self.ids.starts_with(&other.ids)
+    }
+}
+
+impl From<Vec<TinyId>> for IdPath {
+    fn from(ids: Vec<TinyId>) -> Self {
+        Self { ids }
+    }
+}
+
+impl From<IdPath> for Vec<TinyId> {
+    fn from(path: IdPath) -> Self {
+        path.ids
+    }
+}
+
+impl FromIterator<TinyId> for IdPath {
+    fn from_iter<T: IntoIterator<Item = TinyId>>(iter: T) -> Self {
+        Self {
+            ids: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<TinyId> for IdPath {
+    fn extend<T: IntoIterator<Item = TinyId>>(&mut self, iter: T) {
+        self.ids.extend(iter);
+    }
+}
+
+impl std::fmt::Display for IdPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, id) in self.ids.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", Self::SEPARATOR)?;
+            }
+            write!(f, "{id}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for IdPath {
+    type Err = TinyIdError;
+
+    /// Parse an [`IdPath`] from its display form. An empty string is an empty path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::new());
+        }
+        s.split(Self::SEPARATOR).map(str::parse).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn push_pop_parent() {
+        let a = TinyId::from_str_unchecked("aaaaaaaa");
+        let b = TinyId::from_str_unchecked("bbbbbbbb");
+        let c = TinyId::from_str_unchecked("cccccccc");
+
+        let mut path = IdPath::root(a);
+        path.push(b);
+        let child = path.join(c);
+        assert_eq!(child.len(), 3);
+        assert_eq!(child.first(), Some(a));
+        assert_eq!(child.last(), Some(c));
+        assert_eq!(child.parent(), Some(path.clone()));
+        assert!(child.starts_with(&path));
+        assert!(!path.starts_with(&child));
+        assert!(path.starts_with(&IdPath::new()));
+        assert_eq!(child.iter().collect::<Vec<_>>(), [a, b, c]);
+
+        assert_eq!(path.pop(), Some(b));
+        assert_eq!(path.parent(), None);
+        assert_eq!(path.pop(), Some(a));
+        assert_eq!(path.pop(), None);
+        assert!(path.is_empty());
+        assert_eq!(path.first(), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn display_and_parse() {
+        let path = "aaaa1111/bbbb2222/cccc3333".parse::<IdPath>().unwrap();
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.to_string(), "aaaa1111/bbbb2222/cccc3333");
+        assert_eq!("".parse::<IdPath>(), Ok(IdPath::new()));
+        assert_eq!(IdPath::new().to_string(), "");
+
+        for _ in 0..100 {
+            let path = (0..fastrand::usize(1..6))
+                .map(|_| TinyId::random())
+                .collect::<IdPath>();
+            assert_eq!(path.to_string().parse::<IdPath>(), Ok(path));
+        }
+        for bad in [
+            "/",
+            "aaaa1111/",
+            "/aaaa1111",
+            "aaaa1111//bbbb2222",
+            "aaaa111!",
+        ] {
+            assert!(bad.parse::<IdPath>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let path = (0..3).map(|_| TinyId::random()).collect::<IdPath>();
+        let json = serde_json::to_string(&path).unwrap();
+        assert!(json.starts_with('['));
+        assert_eq!(serde_json::from_str::<IdPath>(&json).unwrap(), path);
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{IdProvider, TinyId};
+
+/// A generation strategy for [`TinyId`]s that can be read aloud and remembered, like `bakotefu`,
+/// made up of alternating lowercase consonants and vowels.
+///
+/// ## Entropy
+/// This comes at a steep cost in entropy. A normal random ID has 48 bits of entropy (`64^8`, about
+/// 281 trillion possibilities), while a pronounceable one only has about 25.3 bits
+/// (`16^4 * 5^4`, about 41 million possibilities). By the birthday bound, there is a 50% chance of
+/// a collision after only ~7,500 pronounceable IDs, so they should be paired with a uniqueness
+/// check (e.g. `ConcurrentIdRegistry`) and are best suited to
+/// small sets of short-lived or human-facing IDs.
+///
+/// ## Example
+/// ```
+/// use tinyid::{IdProvider, Pronounceable, TinyId};
+///
+/// let id = TinyId::random_pronounceable();
+/// assert!(id.is_valid());
+/// assert!(Pronounceable::matches(id));
+/// assert!(Pronounceable::matches("bakotefu".parse().unwrap()));
+/// assert!(Pronounceable.next_id().is_valid());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pronounceable;
+
+impl Pronounceable {
+    /// The consonants used in even positions. Letters that are easily confused when spoken
+    /// (`c`, `q`, `w`, `x`, `y`) are left out.
+    pub const CONSONANTS: [u8; 16] = *b"bdfghjklmnprstvz";
+    /// The vowels used in odd positions.
+    pub const VOWELS: [u8; 5] = *b"aeiou";
+
+    /// Generate a new random pronounceable [`TinyId`].
+    #[must_use]
+    pub fn random() -> TinyId {
+        let mut data = TinyId::NULL_DATA;
+        for (i, b) in data.iter_mut().enumerate() {
+            let pool: &[u8] = if i % 2 == 0 {
+                &Self::CONSONANTS
+            } else {
+                &Self::VOWELS
+            };
+            *b = pool[crate::rng::usize_below(pool.len())];
+        }
+        TinyId::from_bytes_unchecked(data)
+    }
+
+    /// Checks whether the given ID follows the pronounceable consonant-vowel pattern.
+    #[must_use]
+    pub fn matches(id: TinyId) -> bool {
+        id.to_bytes().iter().enumerate().all(|(i, b)| {
+            if i % 2 == 0 {
+                Self::CONSONANTS.contains(b)
+            } else {
+                Self::VOWELS.contains(b)
+            }
+        })
+    }
+}
+
+impl IdProvider for Pronounceable {
+    fn next_id(&mut self) -> TinyId {
+        Self::random()
+    }
+}
+
+impl TinyId {
+    /// Create a new random, pronounceable [`TinyId`]. See [`Pronounceable`] for the (much lower)
+    /// entropy of these IDs.
+    #[must_use]
+    pub fn random_pronounceable() -> Self {
+        Pronounceable::random()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn letters_are_valid() {
+        assert!(Pronounceable::CONSONANTS
+            .iter()
+            .chain(&Pronounceable::VOWELS)
+            .all(|&b| TinyId::is_valid_byte(b)));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random() {
+        for _ in 0..1_000 {
+            let id = TinyId::random_pronounceable();
+            assert!(id.is_valid());
+            assert!(Pronounceable::matches(id));
+        }
+        assert!(!Pronounceable::matches(TinyId::from_str_unchecked(
+            "abababab"
+        )));
+        assert!(!Pronounceable::matches(TinyId::null()));
+    }
+}
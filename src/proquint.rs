@@ -0,0 +1,133 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! [Proquint](https://arxiv.org/html/0901.4016) ("PRO-nouncable QUINT-uplet") encoding of the
+//! 64-bit value of a [`TinyId`].
+
+use crate::{Pronounceable, TinyId, TinyIdError};
+
+/// The proquint vowels, 2 bits each. The consonants (4 bits each) are the same as
+/// [`Pronounceable::CONSONANTS`].
+const VOWELS: [u8; 4] = *b"aiou";
+
+/// Encode 16 bits as a consonant-vowel-consonant-vowel-consonant quint.
+fn quint(n: u16) -> [u8; 5] {
+    let c = |shift: u16| Pronounceable::CONSONANTS[usize::from(n >> shift & 0xF)];
+    let v = |shift: u16| VOWELS[usize::from(n >> shift & 0x3)];
+    [c(12), v(10), c(6), v(4), c(0)]
+}
+
+/// Decode a quint (case-insensitive) back to 16 bits, if it is one.
+#[allow(clippy::cast_possible_truncation)]
+fn quint_value(quint: &str) -> Option<u16> {
+    let position = |pool: &[u8], b: u8| {
+        pool.iter()
+            .position(|&p| p == b.to_ascii_lowercase())
+            .map(|i| i as u16)
+    };
+    let c = |b| position(&Pronounceable::CONSONANTS, b);
+    let v = |b| position(&VOWELS, b);
+    match *quint.as_bytes() {
+        [c1, v1, c2, v2, c3] => {
+            Some(c(c1)? << 12 | v(v1)? << 10 | c(c2)? << 6 | v(v2)? << 4 | c(c3)?)
+        }
+        _ => None,
+    }
+}
+
+impl TinyId {
+    /// Convert the 64-bit value of this [`TinyId`] (see [`TinyId::to_u64`]) to four proquints,
+    /// e.g. `"lusab-babad-gutih-tugad"`, for reading identifiers aloud.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let proquint = id.to_proquint();
+    /// assert_eq!(proquint.split('-').count(), 4);
+    /// assert_eq!(TinyId::from_proquint(&proquint), Ok(id));
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn to_proquint(self) -> String {
+        let value = self.to_u64();
+        let quints: Vec<String> = (0..4)
+            .rev()
+            .map(|i| quint((value >> (i * 16)) as u16))
+            .map(|q| q.iter().map(|&b| b as char).collect())
+            .collect();
+        quints.join("-")
+    }
+
+    /// Convert four proquints created by [`TinyId::to_proquint`] back to a [`TinyId`]. The
+    /// proquints may be separated by dashes and/or whitespace, and are case-insensitive.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if there are not exactly four proquints.
+    /// - [`TinyIdError::InvalidCharacters`] if any of the proquints are invalid, or they do not
+    ///   decode to a valid [`TinyId`].
+    pub fn from_proquint(proquint: &str) -> Result<Self, TinyIdError> {
+        let quints: Vec<&str> = proquint
+            .split(|c: char| c == '-' || c.is_whitespace())
+            .filter(|q| !q.is_empty())
+            .collect();
+        if quints.len() != 4 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        let value = quints.iter().try_fold(0u64, |acc, q| {
+            quint_value(q)
+                .map(|n| acc << 16 | u64::from(n))
+                .ok_or(TinyIdError::InvalidCharacters)
+        })?;
+        Self::from_u64(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn quints() {
+        for n in 0..=u16::MAX {
+            let q = quint(n);
+            assert_eq!(quint_value(std::str::from_utf8(&q).unwrap()), Some(n));
+        }
+        // Examples from the proquint specification (IPv4 addresses).
+        assert_eq!(&quint(0x7F00), b"lusab");
+        assert_eq!(&quint(0x0001), b"babad");
+        assert_eq!(quint_value("LUSAB"), Some(0x7F00));
+        assert_eq!(quint_value("lusa"), None);
+        assert_eq!(quint_value("lesab"), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn roundtrip() {
+        for _ in 0..1_000 {
+            let id = TinyId::random();
+            let proquint = id.to_proquint();
+            assert_eq!(TinyId::from_proquint(&proquint), Ok(id));
+            let spaced = proquint.replace('-', " ").to_uppercase();
+            assert_eq!(TinyId::from_proquint(&spaced), Ok(id));
+        }
+        assert_eq!(TinyId::null().to_proquint(), "babab-babab-babab-babab");
+        assert_eq!(
+            TinyId::from_proquint("babab-babab-babab-babab"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::from_proquint("lusab-babad"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_proquint("lusab-babad-lusab-bebad"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
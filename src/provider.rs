@@ -0,0 +1,158 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{index::KEYSPACE, TinyId};
+
+/// A source of [`TinyId`]s.
+///
+/// Services that take an `impl IdProvider` (or a `Box<dyn IdProvider>`) instead of calling
+/// [`TinyId::random`] directly can be handed a [`SequentialProvider`] or [`FixedProvider`] in
+/// tests, making the generated IDs predictable without any conditional compilation.
+///
+/// ## Example
+/// ```
+/// use tinyid::{FixedProvider, IdProvider, RandomProvider, TinyId};
+///
+/// fn create_user(ids: &mut impl IdProvider) -> TinyId {
+///     ids.next_id()
+/// }
+///
+/// assert!(create_user(&mut RandomProvider).is_valid());
+///
+/// let fixed = TinyId::from_str_unchecked("user0001");
+/// assert_eq!(create_user(&mut FixedProvider::new(fixed)), fixed);
+/// ```
+pub trait IdProvider {
+    /// Get the next [`TinyId`] from this provider.
+    fn next_id(&mut self) -> TinyId;
+}
+
+impl<P: IdProvider + ?Sized> IdProvider for &mut P {
+    fn next_id(&mut self) -> TinyId {
+        (**self).next_id()
+    }
+}
+
+impl<P: IdProvider + ?Sized> IdProvider for Box<P> {
+    fn next_id(&mut self) -> TinyId {
+        (**self).next_id()
+    }
+}
+
+/// An [`IdProvider`] that generates a new random [`TinyId`] each time, using [`TinyId::random`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RandomProvider;
+
+impl IdProvider for RandomProvider {
+    fn next_id(&mut self) -> TinyId {
+        TinyId::random()
+    }
+}
+
+/// An [`IdProvider`] that walks through the keyspace in order, starting at `--------` and
+/// wrapping back around after `zzzzzzzz`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequentialProvider {
+    next: u64,
+}
+
+impl SequentialProvider {
+    /// Create a new [`SequentialProvider`] starting at the first ID in the keyspace.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`SequentialProvider`] whose first ID will be `start`.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`](crate::TinyIdError::InvalidCharacters) if `start` is
+    ///   null or contains invalid bytes.
+    pub fn starting_at(start: TinyId) -> Result<Self, crate::TinyIdError> {
+        start
+            .to_index()
+            .map(|next| Self { next })
+            .ok_or(crate::TinyIdError::InvalidCharacters)
+    }
+}
+
+impl IdProvider for SequentialProvider {
+    fn next_id(&mut self) -> TinyId {
+        let id = TinyId::from_index_wrapping(self.next);
+        self.next = (self.next + 1) % KEYSPACE;
+        id
+    }
+}
+
+/// An [`IdProvider`] that always returns the same [`TinyId`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FixedProvider {
+    id: TinyId,
+}
+
+impl FixedProvider {
+    /// Create a new [`FixedProvider`] that will always return `id`.
+    #[must_use]
+    pub fn new(id: TinyId) -> Self {
+        Self { id }
+    }
+}
+
+impl IdProvider for FixedProvider {
+    fn next_id(&mut self) -> TinyId {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random() {
+        let mut provider = RandomProvider;
+        let a = provider.next_id();
+        let b = provider.next_id();
+        assert!(a.is_valid());
+        assert!(b.is_valid());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn sequential() {
+        let mut provider = SequentialProvider::new();
+        assert_eq!(provider.next_id().to_string(), "--------");
+        assert_eq!(provider.next_id().to_string(), "-------0");
+        assert_eq!(provider.next_id().to_string(), "-------1");
+
+        let start = TinyId::from_str_unchecked("aaaaaaaz");
+        let mut provider = SequentialProvider::starting_at(start).unwrap();
+        assert_eq!(provider.next_id(), start);
+        assert_eq!(provider.next_id().to_string(), "aaaaaab-");
+
+        let mut provider =
+            SequentialProvider::starting_at(TinyId::from_str_unchecked("zzzzzzzz")).unwrap();
+        provider.next_id();
+        assert_eq!(provider.next_id().to_string(), "--------");
+
+        assert!(SequentialProvider::starting_at(TinyId::null()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn fixed_and_dyn() {
+        fn take_two(mut provider: impl IdProvider) -> (TinyId, TinyId) {
+            (provider.next_id(), provider.next_id())
+        }
+
+        let id = TinyId::random();
+        let mut provider: Box<dyn IdProvider> = Box::new(FixedProvider::new(id));
+        assert_eq!(provider.next_id(), id);
+        assert_eq!(take_two(&mut provider), (id, id));
+    }
+}
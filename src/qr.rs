@@ -0,0 +1,141 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Write;
+
+use qrcode::{Color, QrCode};
+
+use crate::{TinyId, TinyIdError};
+
+/// The number of light modules drawn around a QR code by [`TinyId::to_qr_svg`], the minimum the
+/// QR specification requires for reliable scanning.
+const QUIET_ZONE: usize = 4;
+
+impl TinyId {
+    /// Encode this [`TinyId`] as a QR code, returning its grid of modules indexed by row and then
+    /// column, where `true` is dark. The encoded text is exactly the ID's display form, so
+    /// scanning it gives back the same string [`ToString::to_string`] does. The grid does not
+    /// include the quiet zone; leave at least four light modules around it when drawing it.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if this ID is null or not valid.
+    /// - [`TinyIdError::Conversion`] if the QR encoder fails, which should not happen for IDs.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let matrix = TinyId::random().to_qr_matrix().unwrap();
+    /// assert_eq!(matrix.len(), 21);
+    /// assert!(matrix.iter().all(|row| row.len() == 21));
+    /// assert!(TinyId::null().to_qr_matrix().is_err());
+    /// ```
+    pub fn to_qr_matrix(&self) -> Result<Vec<Vec<bool>>, TinyIdError> {
+        let code = self.qr_code()?;
+        let width = code.width();
+        let matrix = code
+            .into_colors()
+            .chunks(width)
+            .map(|row| row.iter().map(|&color| color == Color::Dark).collect())
+            .collect();
+        Ok(matrix)
+    }
+
+    /// Render this [`TinyId`] as a QR code in an SVG image `size` pixels wide and tall, with a
+    /// white background and quiet zone, e.g. for printing on physical labels and tickets. See
+    /// [`TinyId::to_qr_matrix`].
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if this ID is null or not valid.
+    /// - [`TinyIdError::Conversion`] if the QR encoder fails, which should not happen for IDs.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let svg = TinyId::random().to_qr_svg(256).unwrap();
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_qr_svg(&self, size: u32) -> Result<String, TinyIdError> {
+        let matrix = self.to_qr_matrix()?;
+        let n = matrix.len() + 2 * QUIET_ZONE;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+             viewBox=\"0 0 {n} {n}\" shape-rendering=\"crispEdges\">\
+             <rect width=\"{n}\" height=\"{n}\" fill=\"#fff\"/><g fill=\"#000\">",
+        );
+        for (y, row) in matrix.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &dark)| dark) {
+                // Writing to a `String` can not fail.
+                let _ = write!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\"/>",
+                    x + QUIET_ZONE,
+                    y + QUIET_ZONE
+                );
+            }
+        }
+        svg.push_str("</g></svg>");
+        Ok(svg)
+    }
+
+    /// Encode the display form of this ID as a QR code.
+    fn qr_code(self) -> Result<QrCode, TinyIdError> {
+        if !self.is_valid() {
+            return Err(TinyIdError::InvalidCharacters);
+        }
+        QrCode::new(self.to_string()).map_err(|err| TinyIdError::Conversion(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn matrix() {
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        let matrix = id.to_qr_matrix().unwrap();
+        assert_eq!(matrix, id.to_qr_matrix().unwrap());
+        assert_eq!(matrix.len(), 21);
+        // The top left finder pattern.
+        assert!(matrix[0][..7].iter().all(|&dark| dark));
+        assert!(!matrix[1][1] && matrix[3][3]);
+        assert_ne!(
+            matrix,
+            TinyId::from_str_unchecked("abcdefgi")
+                .to_qr_matrix()
+                .unwrap()
+        );
+        assert_eq!(
+            TinyId::null().to_qr_matrix(),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::from_bytes_unchecked(*b"abc\0efgh").to_qr_svg(10),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn svg() {
+        let id = TinyId::random();
+        let dark = id
+            .to_qr_matrix()
+            .unwrap()
+            .iter()
+            .flatten()
+            .filter(|&&d| d)
+            .count();
+        let svg = id.to_qr_svg(290).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"290\""));
+        assert!(svg.contains("viewBox=\"0 0 29 29\""));
+        assert!(svg.ends_with("</g></svg>"));
+        assert_eq!(svg.matches("<rect").count(), dark + 1);
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// A contiguous, non-empty range of the keyspace, from `start` to `end` inclusive in [`Ord`]
+/// order, for sharded systems that need to describe and divide ownership of portions of the ID
+/// space.
+///
+/// Displayed as `start..=end`, and iterates over every ID in the range in order. Unlike a
+/// [`Block`](crate::Block), a range can start and end anywhere, and can be [`split`] into
+/// smaller ranges.
+///
+/// [`split`]: TinyIdRange::split
+///
+/// ## Example
+/// ```
+/// use tinyid::{TinyId, TinyIdRange};
+///
+/// let shards = TinyIdRange::FULL.split(4);
+/// assert_eq!(shards.len(), 4);
+/// let id = TinyId::random();
+/// assert_eq!(shards.iter().filter(|shard| shard.contains(id)).count(), 1);
+/// assert_eq!(shards[0].to_string(), "--------..=Ezzzzzzz");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RangeState")
+)]
+pub struct TinyIdRange {
+    start: TinyId,
+    end: TinyId,
+}
+
+impl TinyIdRange {
+    /// The range covering the whole keyspace, `--------..=zzzzzzzz`.
+    pub const FULL: Self = Self {
+        start: TinyId { data: *b"--------" },
+        end: TinyId { data: *b"zzzzzzzz" },
+    };
+
+    /// The string separating the two ends in the display form of a [`TinyIdRange`].
+    pub const SEPARATOR: &'static str = "..=";
+
+    /// Create the range from `start` to `end` inclusive. The ends may be given in either order.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if either end is not a valid [`TinyId`].
+    pub fn new(start: TinyId, end: TinyId) -> Result<Self, TinyIdError> {
+        if !start.is_valid() || !end.is_valid() {
+            return Err(TinyIdError::InvalidCharacters);
+        }
+        Ok(Self {
+            start: start.min(end),
+            end: start.max(end),
+        })
+    }
+
+    /// Create the range between two keyspace indices, inclusive and in order.
+    fn from_indices(start: u64, end: u64) -> Self {
+        Self {
+            start: TinyId::from_index_wrapping(start),
+            end: TinyId::from_index_wrapping(end),
+        }
+    }
+
+    /// The keyspace index of the start of this range.
+    fn start_index(self) -> u64 {
+        self.start.to_index().unwrap_or_default()
+    }
+
+    /// The keyspace index of the end of this range.
+    fn end_index(self) -> u64 {
+        self.end.to_index().unwrap_or_default()
+    }
+
+    /// The first ID in this range.
+    #[must_use]
+    pub fn start(self) -> TinyId {
+        self.start
+    }
+
+    /// The last ID in this range.
+    #[must_use]
+    pub fn end(self) -> TinyId {
+        self.end
+    }
+
+    /// The number of IDs in this range, which is always at least 1.
+    #[allow(clippy::len_without_is_empty)]
+    #[must_use]
+    pub fn len(self) -> u64 {
+        self.end_index().saturating_sub(self.start_index()) + 1
+    }
+
+    /// Checks whether the given ID is within this range. Invalid IDs are never contained.
+    #[must_use]
+    pub fn contains(self, id: TinyId) -> bool {
+        id.is_valid() && self.start <= id && id <= self.end
+    }
+
+    /// Split this range into `n` contiguous ranges, in order, which together cover exactly this
+    /// range. Their lengths differ by at most 1, with any longer ranges first. `n` is clamped to
+    /// `1..=self.len()`, so every returned range is non-empty.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{TinyId, TinyIdRange};
+    ///
+    /// let range = TinyIdRange::new(
+    ///     TinyId::from_str_unchecked("--------"),
+    ///     TinyId::from_str_unchecked("-------8"),
+    /// )
+    /// .unwrap();
+    /// let parts = range.split(3);
+    /// assert_eq!(parts.iter().map(|p| p.len()).collect::<Vec<_>>(), [4, 3, 3]);
+    /// assert_eq!(parts[1].to_string(), "-------3..=-------5");
+    /// ```
+    #[must_use]
+    pub fn split(self, n: usize) -> Vec<Self> {
+        let len = self.len();
+        let n = u64::try_from(n).unwrap_or(u64::MAX).clamp(1, len);
+        let (base, extra) = (len / n, len % n);
+        let mut start = self.start_index();
+        (0..n)
+            .map(|i| {
+                let size = base + u64::from(i < extra);
+                let part = Self::from_indices(start, start + size - 1);
+                start += size;
+                part
+            })
+            .collect()
+    }
+
+    /// Iterate over every ID in this range, in order.
+    #[must_use]
+    pub fn iter(self) -> TinyIdRangeIter {
+        TinyIdRangeIter {
+            next: self.start_index(),
+            end: self.end_index() + 1,
+        }
+    }
+}
+
+impl Default for TinyIdRange {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl IntoIterator for TinyIdRange {
+    type Item = TinyId;
+    type IntoIter = TinyIdRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Display for TinyIdRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.start, Self::SEPARATOR, self.end)
+    }
+}
+
+impl std::str::FromStr for TinyIdRange {
+    type Err = TinyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once(Self::SEPARATOR)
+            .ok_or(TinyIdError::InvalidCharacters)?;
+        Self::new(start.parse()?, end.parse()?)
+    }
+}
+
+/// The serialized form of a [`TinyIdRange`], which is passed through [`TinyIdRange::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "TinyIdRange")]
+struct RangeState {
+    start: TinyId,
+    end: TinyId,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RangeState> for TinyIdRange {
+    type Error = TinyIdError;
+
+    fn try_from(state: RangeState) -> Result<Self, Self::Error> {
+        Self::new(state.start, state.end)
+    }
+}
+
+/// An iterator over the IDs in a [`TinyIdRange`], in order.
+#[derive(Clone, Debug)]
+pub struct TinyIdRangeIter {
+    next: u64,
+    end: u64,
+}
+
+impl Iterator for TinyIdRangeIter {
+    type Item = TinyId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        let id = TinyId::from_index_wrapping(self.next);
+        self.next += 1;
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.end - self.next).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for TinyIdRangeIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(TinyId::from_index_wrapping(self.end))
+    }
+}
+
+impl ExactSizeIterator for TinyIdRangeIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::KEYSPACE;
+
+    fn id(s: &str) -> TinyId {
+        TinyId::from_str_unchecked(s)
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn construction() {
+        let range = TinyIdRange::new(id("bbbbbbbb"), id("aaaaaaaa")).unwrap();
+        assert_eq!(range.start(), id("aaaaaaaa"));
+        assert_eq!(range.end(), id("bbbbbbbb"));
+        assert_eq!(
+            TinyIdRange::new(TinyId::null(), id("aaaaaaaa")),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(TinyIdRange::FULL.len(), KEYSPACE);
+        assert_eq!(TinyIdRange::default(), TinyIdRange::FULL);
+
+        let single = TinyIdRange::new(id("aaaaaaaa"), id("aaaaaaaa")).unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.iter().collect::<Vec<_>>(), [id("aaaaaaaa")]);
+
+        assert_eq!(range.to_string(), "aaaaaaaa..=bbbbbbbb");
+        assert_eq!(range.to_string().parse(), Ok(range));
+        for bad in [
+            "aaaaaaaa",
+            "aaaaaaaa..=",
+            "aaaaaaaa..bbbbbbbb",
+            "aaaaaaa!..=bbbbbbbb",
+        ] {
+            assert!(bad.parse::<TinyIdRange>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let range = TinyIdRange::new(id("aaaaaaaa"), id("bbbbbbbb")).unwrap();
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(serde_json::from_str::<TinyIdRange>(&json).unwrap(), range);
+
+        let reversed = json
+            .replace("start", "tmp")
+            .replace("end", "start")
+            .replace("tmp", "end");
+        assert_eq!(
+            serde_json::from_str::<TinyIdRange>(&reversed).unwrap(),
+            range
+        );
+
+        let null_end = serde_json::json!({ "start": id("aaaaaaaa"), "end": TinyId::null() });
+        assert!(serde_json::from_value::<TinyIdRange>(null_end).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn contains_and_iter() {
+        let range = TinyIdRange::new(id("aaaaaa--"), id("aaaaaa-z")).unwrap();
+        assert_eq!(range.len(), 64);
+        let ids: Vec<TinyId> = range.into_iter().collect();
+        assert_eq!(ids.len(), 64);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids.iter().all(|&id| range.contains(id)));
+        assert_eq!(ids.first(), Some(&range.start()));
+        assert_eq!(ids.last(), Some(&range.end()));
+        assert!(!range.contains(id("aaaaaa0-")));
+        assert!(!range.contains(id("aaaaa-zz")));
+        assert!(!range.contains(TinyId::null()));
+        assert!(!range.contains(TinyId::from_bytes_unchecked(*b"aaaaaa-\x7f")));
+
+        let mut iter = range.iter();
+        assert_eq!(iter.len(), 64);
+        assert_eq!(iter.next_back(), Some(range.end()));
+        assert_eq!(iter.next(), Some(range.start()));
+        assert_eq!(iter.len(), 62);
+        assert_eq!(
+            iter.rev().collect::<Vec<_>>(),
+            ids[1..63].iter().rev().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn split() {
+        for n in [1, 2, 3, 7, 64, 1_000] {
+            let parts = TinyIdRange::FULL.split(n);
+            assert_eq!(parts.len(), n);
+            assert_eq!(parts[0].start(), TinyIdRange::FULL.start());
+            assert_eq!(parts[n - 1].end(), TinyIdRange::FULL.end());
+            assert_eq!(parts.iter().map(|p| p.len()).sum::<u64>(), KEYSPACE);
+            for pair in parts.windows(2) {
+                assert_eq!(pair[0].end().checked_add(1), Some(pair[1].start()));
+                assert!(pair[0].len() - pair[1].len() <= 1);
+            }
+        }
+
+        let range = TinyIdRange::new(id("--------"), id("-------1")).unwrap();
+        assert_eq!(range.split(0), [range]);
+        let singles = range.split(10);
+        assert_eq!(singles.len(), 3);
+        assert!(singles.iter().all(|p| p.len() == 1));
+    }
+}
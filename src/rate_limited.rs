@@ -0,0 +1,228 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{IdProvider, TinyId, TinyIdError};
+
+/// The token bucket and wrapped provider of a [`RateLimitedGenerator`].
+#[derive(Debug)]
+struct State<P> {
+    inner: P,
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// Wraps an [`IdProvider`] in a token bucket, so that public endpoints which mint IDs can not be
+/// used to exhaust a registry, block, or unique generator.
+///
+/// The bucket holds up to `burst` tokens (by default, one second's worth) and refills at
+/// `per_second` tokens per second; each generated ID takes one token. When the bucket is empty,
+/// [`RateLimitedGenerator::try_next_id`] fails immediately instead of waiting, and
+/// [`RateLimitedGenerator::retry_after`] says how long until the next ID is available, e.g. for
+/// a `Retry-After` header.
+///
+/// The generator is `Send + Sync` (if the provider is `Send`) and every method takes `&self`, so
+/// one instance can be shared by every request handler.
+///
+/// ## Example
+/// ```
+/// use tinyid::{RandomProvider, RateLimitedGenerator};
+///
+/// let gen = RateLimitedGenerator::new(RandomProvider, 10).with_burst(2);
+/// assert!(gen.try_next_id().is_ok());
+/// assert!(gen.try_next_id().is_ok());
+/// assert!(gen.try_next_id().is_err());
+/// assert!(gen.retry_after() <= std::time::Duration::from_millis(100));
+/// ```
+#[derive(Debug)]
+pub struct RateLimitedGenerator<P> {
+    per_second: u32,
+    burst: u32,
+    state: Mutex<State<P>>,
+}
+
+impl<P: IdProvider> RateLimitedGenerator<P> {
+    /// Create a new [`RateLimitedGenerator`] allowing `inner` to generate `per_second` (clamped
+    /// to at least 1) IDs per second on average, with bursts of up to one second's worth. The
+    /// bucket starts full.
+    #[must_use]
+    pub fn new(inner: P, per_second: u32) -> Self {
+        let per_second = per_second.max(1);
+        Self {
+            per_second,
+            burst: per_second,
+            state: Mutex::new(State {
+                inner,
+                tokens: f64::from(per_second),
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Allow bursts of up to `burst` (clamped to at least 1) IDs, and refill the bucket to that
+    /// size.
+    #[must_use]
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self.state_mut().tokens = f64::from(self.burst);
+        self
+    }
+
+    /// The average number of IDs that can be generated per second.
+    #[must_use]
+    pub fn per_second(&self) -> u32 {
+        self.per_second
+    }
+
+    /// The maximum number of IDs that can be generated at once.
+    #[must_use]
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Generate the next [`TinyId`] from the wrapped provider, if the rate limit allows it.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the rate limit has been reached, see
+    ///   [`RateLimitedGenerator::retry_after`].
+    pub fn try_next_id(&self) -> Result<TinyId, TinyIdError> {
+        self.try_next_id_at(Instant::now())
+    }
+
+    /// The number of IDs that can be generated right now.
+    #[must_use]
+    pub fn available(&self) -> u32 {
+        let mut state = self.lock();
+        self.refill(&mut state, Instant::now());
+        // Tokens are clamped to `0..=burst`, so this can not truncate.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let available = state.tokens.floor() as u32;
+        available
+    }
+
+    /// How long until the next ID can be generated, which is zero if one can be generated now.
+    #[must_use]
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after_at(Instant::now())
+    }
+
+    /// Unwrap the provider this generator was created with.
+    pub fn into_inner(self) -> P {
+        self.state
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .inner
+    }
+
+    /// [`RateLimitedGenerator::try_next_id`], with the current time given.
+    fn try_next_id_at(&self, now: Instant) -> Result<TinyId, TinyIdError> {
+        let mut state = self.lock();
+        self.refill(&mut state, now);
+        if state.tokens < 1.0 {
+            return Err(TinyIdError::GenerationFailure);
+        }
+        state.tokens -= 1.0;
+        Ok(state.inner.next_id())
+    }
+
+    /// [`RateLimitedGenerator::retry_after`], with the current time given.
+    fn retry_after_at(&self, now: Instant) -> Duration {
+        let mut state = self.lock();
+        self.refill(&mut state, now);
+        let missing = (1.0 - state.tokens).max(0.0);
+        Duration::from_secs_f64(missing / f64::from(self.per_second))
+    }
+
+    /// Add the tokens earned since the bucket was last refilled.
+    fn refill(&self, state: &mut State<P>, now: Instant) {
+        let elapsed = now.saturating_duration_since(state.refilled);
+        state.tokens = (state.tokens + elapsed.as_secs_f64() * f64::from(self.per_second))
+            .min(f64::from(self.burst));
+        state.refilled = state.refilled.max(now);
+    }
+
+    /// Lock the bucket. A panic while it was locked can not leave it inconsistent, so poisoning
+    /// is ignored.
+    fn lock(&self) -> std::sync::MutexGuard<'_, State<P>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the bucket without locking it.
+    fn state_mut(&mut self) -> &mut State<P> {
+        self.state.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RandomProvider, SequentialProvider};
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn token_bucket() {
+        let gen = RateLimitedGenerator::new(SequentialProvider::new(), 10).with_burst(3);
+        assert_eq!(gen.per_second(), 10);
+        assert_eq!(gen.burst(), 3);
+        let start = gen.lock().refilled;
+
+        let ids = (0..3)
+            .map(|_| gen.try_next_id_at(start).unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["--------", "-------0", "-------1"]);
+        assert_eq!(
+            gen.try_next_id_at(start),
+            Err(TinyIdError::GenerationFailure)
+        );
+        assert_eq!(gen.retry_after_at(start), Duration::from_millis(100));
+        assert_eq!(
+            gen.retry_after_at(start + Duration::from_millis(40)),
+            Duration::from_millis(60)
+        );
+
+        // One token has been earned after 100ms, and the bucket never holds more than `burst`.
+        let later = start + Duration::from_millis(100);
+        assert!(gen.try_next_id_at(later).is_ok());
+        assert!(gen.try_next_id_at(later).is_err());
+        let much_later = later + Duration::from_secs(30);
+        assert_eq!(gen.retry_after_at(much_later), Duration::ZERO);
+        for _ in 0..3 {
+            assert!(gen.try_next_id_at(much_later).is_ok());
+        }
+        assert!(gen.try_next_id_at(much_later).is_err());
+
+        // Going back in time does not earn tokens.
+        assert!(gen.try_next_id_at(start).is_err());
+        assert_eq!(gen.into_inner().next_id().to_string(), "-------6");
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn defaults_and_threads() {
+        let gen = RateLimitedGenerator::new(RandomProvider, 0);
+        assert_eq!(gen.per_second(), 1);
+        assert_eq!(gen.burst(), 1);
+        assert_eq!(gen.with_burst(0).burst(), 1);
+
+        let gen = RateLimitedGenerator::new(RandomProvider, 100);
+        assert_eq!(gen.available(), 100);
+        let generated = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| (0..50).filter(|_| gen.try_next_id().is_ok()).count()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .sum::<usize>()
+        });
+        // A few tokens may be earned while the threads run.
+        assert!((100..110).contains(&generated), "{generated}");
+    }
+}
@@ -0,0 +1,568 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use dashmap::{mapref::entry::Entry, DashMap};
+
+use crate::index::KEYSPACE;
+use crate::stats::Counters;
+use crate::{GenerationStats, TinyId};
+
+/// The magic bytes at the start of a saved registry.
+const MAGIC: [u8; 4] = *b"TIDR";
+/// The current version of the saved registry format.
+const VERSION: u8 = 2;
+/// The expiry written for IDs which never expire.
+const NO_EXPIRY: u64 = u64::MAX;
+
+/// A thread-safe set of claimed [`TinyId`]s, backed by a [`DashMap`].
+///
+/// Unlike [`UniqueGenerator`](crate::UniqueGenerator), which only ever hands out new IDs, a
+/// registry tracks IDs which come and go (e.g. live session IDs), so claimed IDs can be released
+/// again and have no fixed capacity.
+///
+/// IDs can also be claimed for a limited time with [`ConcurrentIdRegistry::claim_for`]. Once an
+/// ID has expired it is treated as released (it can be claimed again and is no longer
+/// [`contained`](ConcurrentIdRegistry::contains)), although it keeps using memory until
+/// [`ConcurrentIdRegistry::purge_expired`] is called. Saved registries record when each ID
+/// expires on the wall clock, so time spent offline counts towards expiry.
+///
+/// ## Example
+/// ```
+/// use tinyid::{ConcurrentIdRegistry, TinyId};
+///
+/// let sessions = ConcurrentIdRegistry::new();
+/// let id = sessions.claim_random();
+/// assert!(sessions.contains(id));
+/// assert!(!sessions.claim(id));
+/// assert!(sessions.release(id));
+/// assert!(!sessions.contains(id));
+/// ```
+#[derive(Debug, Default)]
+pub struct ConcurrentIdRegistry {
+    /// Each claimed ID, along with the instant it expires (if it does).
+    ids: DashMap<TinyId, Option<Instant>>,
+    counters: Counters,
+}
+
+impl ConcurrentIdRegistry {
+    /// Create a new, empty [`ConcurrentIdRegistry`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty [`ConcurrentIdRegistry`] with room for at least `capacity` IDs before
+    /// reallocating.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ids: DashMap::with_capacity(capacity),
+            counters: Counters::default(),
+        }
+    }
+
+    /// Claims the given ID, returning `true` if it was not already claimed.
+    #[must_use]
+    pub fn claim(&self, id: TinyId) -> bool {
+        self.claim_until(id, None)
+    }
+
+    /// Claims the given ID until `ttl` has passed, returning `true` if it was not already
+    /// claimed.
+    #[must_use]
+    pub fn claim_for(&self, id: TinyId, ttl: Duration) -> bool {
+        self.claim_until(id, Instant::now().checked_add(ttl))
+    }
+
+    /// Generates and claims a new random ID which was not already claimed.
+    #[must_use]
+    pub fn claim_random(&self) -> TinyId {
+        self.counters.time(|| loop {
+            let id = TinyId::random();
+            if self.claim(id) {
+                return id;
+            }
+            self.counters.retry();
+        })
+    }
+
+    /// Releases the given ID, returning `true` if it was claimed (and had not expired).
+    #[must_use]
+    pub fn release(&self, id: TinyId) -> bool {
+        let now = Instant::now();
+        self.ids
+            .remove(&id)
+            .is_some_and(|(_, expiry)| !is_expired(expiry, now))
+    }
+
+    /// Checks whether the given ID is currently claimed (and has not expired).
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        let now = Instant::now();
+        self.ids
+            .get(&id)
+            .is_some_and(|expiry| !is_expired(*expiry, now))
+    }
+
+    /// Removes every expired ID, returning how many were removed.
+    #[allow(clippy::must_use_candidate)]
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let before = self.ids.len();
+        self.ids.retain(|_, expiry| !is_expired(*expiry, now));
+        before.saturating_sub(self.ids.len())
+    }
+
+    /// The number of claimed IDs, including any expired IDs which have not been purged yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks whether no IDs are claimed, including any expired IDs which have not been purged
+    /// yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Releases every claimed ID.
+    pub fn clear(&self) {
+        self.ids.clear();
+    }
+
+    /// Get a snapshot of this registry's activity. Collisions count every rejected claim, while
+    /// retries only count those made by [`ConcurrentIdRegistry::claim_random`]. Occupancy is the
+    /// fraction of every possible ID that is currently stored (including expired IDs which have
+    /// not been purged).
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::ConcurrentIdRegistry;
+    ///
+    /// let registry = ConcurrentIdRegistry::new();
+    /// let id = registry.claim_random();
+    /// assert!(!registry.claim(id));
+    /// let stats = registry.stats();
+    /// assert_eq!(stats.issued, 1);
+    /// assert_eq!(stats.collisions, 1);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn stats(&self) -> GenerationStats {
+        self.counters
+            .snapshot(self.ids.len() as f64 / KEYSPACE as f64)
+    }
+
+    /// Claims `id` until `expiry`, replacing it if it was claimed but has expired.
+    fn claim_until(&self, id: TinyId, expiry: Option<Instant>) -> bool {
+        let claimed = match self.ids.entry(id) {
+            Entry::Occupied(mut entry) => {
+                if is_expired(*entry.get(), Instant::now()) {
+                    entry.insert(expiry);
+                    true
+                } else {
+                    false
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(expiry);
+                true
+            }
+        };
+        if claimed {
+            self.counters.issued();
+        } else {
+            self.counters.collision();
+        }
+        claimed
+    }
+
+    /// Every claimed ID which has not expired, along with when it expires in milliseconds since
+    /// the UNIX epoch (`u64::MAX` if it never expires).
+    fn live_entries(&self) -> Vec<(TinyId, u64)> {
+        let (now, system_now) = (Instant::now(), SystemTime::now());
+        self.ids
+            .iter()
+            .filter(|entry| !is_expired(*entry.value(), now))
+            .map(|entry| {
+                let millis = expiry_to_unix_millis(*entry.value(), now, system_now);
+                (*entry.key(), millis)
+            })
+            .collect()
+    }
+
+    /// Creates a registry from saved entries, dropping any which expired while they were saved.
+    fn from_saved_entries(entries: impl ExactSizeIterator<Item = (TinyId, u64)>) -> Self {
+        let registry = Self::with_capacity(entries.len());
+        let (now, system_now) = (Instant::now(), SystemTime::now());
+        for (id, millis) in entries {
+            if millis == NO_EXPIRY {
+                registry.ids.insert(id, None);
+            } else if let Some(remaining) = time_until_unix_millis(millis, system_now) {
+                registry.ids.insert(id, now.checked_add(remaining));
+            }
+        }
+        registry
+    }
+
+    /// Saves every claimed ID to the file at `path`, creating or truncating it. See
+    /// [`ConcurrentIdRegistry::write_to`] for the format.
+    ///
+    /// ## Errors
+    /// Any I/O error encountered while creating or writing the file.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut file)?;
+        file.flush()
+    }
+
+    /// Loads a registry previously saved with [`ConcurrentIdRegistry::save_to`].
+    ///
+    /// ## Errors
+    /// Any I/O error encountered while reading the file, or [`io::ErrorKind::InvalidData`] if it
+    /// is not a saved registry or contains an invalid ID.
+    pub fn load_from(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Writes every claimed ID to `writer` in a compact binary format: the magic bytes `TIDR`, a
+    /// format version byte, the number of IDs as a big-endian `u64`, and then each ID as a
+    /// big-endian `u64` (see [`TinyId::to_u64`]) followed by the time it expires, in milliseconds
+    /// since the UNIX epoch, as a big-endian `u64` (`u64::MAX` if it never expires). Expired IDs
+    /// are not written.
+    ///
+    /// ## Errors
+    /// Any I/O error returned by `writer`.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let entries = self.live_entries();
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(entries.len() as u64).to_be_bytes())?;
+        for (id, millis) in entries {
+            writer.write_all(&id.to_u64().to_be_bytes())?;
+            writer.write_all(&millis.to_be_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads a registry written by [`ConcurrentIdRegistry::write_to`], dropping any IDs which
+    /// expired since it was written. Registries saved before expiry was supported (format version
+    /// 1, IDs only) can also be read.
+    ///
+    /// ## Errors
+    /// Any I/O error returned by `reader`, or [`io::ErrorKind::InvalidData`] if the data is not
+    /// a saved registry or contains an invalid ID.
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+        let version = header[4];
+        if header[..4] != MAGIC || !(1..=VERSION).contains(&version) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a saved TinyId registry",
+            ));
+        }
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        let count = u64::from_be_bytes(buf);
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            reader.read_exact(&mut buf)?;
+            let id = TinyId::from_bytes(buf)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            let mut millis = NO_EXPIRY;
+            if version >= 2 {
+                reader.read_exact(&mut buf)?;
+                millis = u64::from_be_bytes(buf);
+            }
+            entries.push((id, millis));
+        }
+        Ok(Self::from_saved_entries(entries.into_iter()))
+    }
+}
+
+/// Checks whether an entry with the given expiry has expired as of `now`.
+fn is_expired(expiry: Option<Instant>, now: Instant) -> bool {
+    expiry.is_some_and(|expiry| expiry <= now)
+}
+
+/// Converts an expiry to the time it happens in milliseconds since the UNIX epoch, given the
+/// current time on both clocks, or [`NO_EXPIRY`] if it never expires.
+fn expiry_to_unix_millis(expiry: Option<Instant>, now: Instant, system_now: SystemTime) -> u64 {
+    expiry.map_or(NO_EXPIRY, |expiry| {
+        system_now
+            .checked_add(expiry.saturating_duration_since(now))
+            .and_then(|at| at.duration_since(UNIX_EPOCH).ok())
+            .map_or(NO_EXPIRY - 1, |since_epoch| {
+                u64::try_from(since_epoch.as_millis())
+                    .map_or(NO_EXPIRY - 1, |millis| millis.min(NO_EXPIRY - 1))
+            })
+    })
+}
+
+/// How long until a time in milliseconds since the UNIX epoch, or `None` if it has already
+/// passed as of `system_now`.
+fn time_until_unix_millis(millis: u64, system_now: SystemTime) -> Option<Duration> {
+    (UNIX_EPOCH + Duration::from_millis(millis))
+        .duration_since(system_now)
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Serializes the registry as a sequence of `(id, expiry)` pairs, where the expiry is the time it
+/// happens in milliseconds since the UNIX epoch (or `null` if the ID never expires), skipping any
+/// expired IDs.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConcurrentIdRegistry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.live_entries()
+                .into_iter()
+                .map(|(id, millis)| (id, (millis != NO_EXPIRY).then_some(millis))),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ConcurrentIdRegistry {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let entries = Vec::<(TinyId, Option<u64>)>::deserialize(deserializer)?;
+        if entries.iter().any(|(id, _)| !id.is_valid()) {
+            return Err(D::Error::custom(
+                "ConcurrentIdRegistry cannot contain an invalid ID",
+            ));
+        }
+        Ok(Self::from_saved_entries(
+            entries
+                .into_iter()
+                .map(|(id, millis)| (id, millis.unwrap_or(NO_EXPIRY))),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn basic_usage() {
+        let registry = ConcurrentIdRegistry::with_capacity(4);
+        assert!(registry.is_empty());
+        let id = TinyId::random();
+        assert!(registry.claim(id));
+        assert!(!registry.claim(id));
+        assert!(registry.contains(id));
+        let other = registry.claim_random();
+        assert_ne!(id, other);
+        assert_eq!(registry.len(), 2);
+        assert!(registry.release(id));
+        assert!(!registry.release(id));
+        assert!(!registry.contains(id));
+        registry.clear();
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn stats() {
+        let registry = ConcurrentIdRegistry::new();
+        assert_eq!(registry.stats(), GenerationStats::default());
+        let id = TinyId::random();
+        assert!(registry.claim(id));
+        assert!(!registry.claim(id));
+        assert!(!registry.claim_for(id, Duration::from_secs(30)));
+        let _ = registry.claim_random();
+        let stats = registry.stats();
+        assert_eq!(stats.issued, 2);
+        assert_eq!(stats.collisions, 2 + stats.retries);
+        assert!(stats.occupancy > 0.0 && stats.occupancy < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn expiry() {
+        let registry = ConcurrentIdRegistry::new();
+        let expired = TinyId::random();
+        let live = TinyId::random();
+        assert!(registry.claim_for(expired, Duration::ZERO));
+        assert!(registry.claim_for(live, Duration::from_secs(1_000)));
+        assert!(!registry.contains(expired));
+        assert!(registry.contains(live));
+        assert!(!registry.claim(live));
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.purge_expired(), 1);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.purge_expired(), 0);
+
+        assert!(registry.claim_for(expired, Duration::ZERO));
+        assert!(!registry.release(expired));
+        assert!(registry.claim_for(expired, Duration::ZERO));
+        assert!(registry.claim(expired));
+        assert!(registry.contains(expired));
+        assert!(registry.release(live));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn persistence() {
+        let registry = ConcurrentIdRegistry::new();
+        for _ in 0..100 {
+            let _ = registry.claim_random();
+        }
+        let mut buf = Vec::new();
+        registry.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), 4 + 1 + 8 + 100 * 16);
+        let loaded = ConcurrentIdRegistry::read_from(buf.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 100);
+        assert!(registry
+            .ids
+            .iter()
+            .all(|entry| loaded.contains(*entry.key())));
+
+        let id = TinyId::random();
+        let mut v1 = b"TIDR\x01".to_vec();
+        v1.extend_from_slice(&1u64.to_be_bytes());
+        v1.extend_from_slice(&id.to_bytes());
+        let loaded = ConcurrentIdRegistry::read_from(v1.as_slice()).unwrap();
+        assert!(loaded.contains(id));
+
+        let ttl = ConcurrentIdRegistry::new();
+        let short = TinyId::random();
+        let long = TinyId::random();
+        assert!(ttl.claim_for(short, Duration::ZERO));
+        assert!(ttl.claim_for(long, Duration::from_secs(1_000)));
+        let mut buf = Vec::new();
+        ttl.write_to(&mut buf).unwrap();
+        let loaded = ConcurrentIdRegistry::read_from(buf.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.contains(long));
+        assert!(loaded.ids.get(&long).unwrap().is_some());
+
+        let path = std::env::temp_dir().join(format!("tinyid-registry-{}", TinyId::random()));
+        registry.save_to(&path).unwrap();
+        let loaded = ConcurrentIdRegistry::load_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.len(), 100);
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let (expired, live) = (TinyId::random(), TinyId::random());
+        let mut saved = b"TIDR\x02".to_vec();
+        saved.extend_from_slice(&2u64.to_be_bytes());
+        for (id, at) in [
+            (expired, now.saturating_sub(Duration::from_secs(1))),
+            (live, now * 2),
+        ] {
+            saved.extend_from_slice(&id.to_bytes());
+            saved.extend_from_slice(&u64::try_from(at.as_millis()).unwrap().to_be_bytes());
+        }
+        let loaded = ConcurrentIdRegistry::read_from(saved.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.contains(live));
+
+        let err = ConcurrentIdRegistry::read_from(&b"nope, not a registry"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = ConcurrentIdRegistry::read_from(&buf[..20]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        for bad in [TinyId::null().to_bytes(), *b"abcdefg!"] {
+            let mut v1 = b"TIDR\x01".to_vec();
+            v1.extend_from_slice(&1u64.to_be_bytes());
+            v1.extend_from_slice(&bad);
+            let err = ConcurrentIdRegistry::read_from(v1.as_slice()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn wall_clock_expiry() {
+        let (now, system_now) = (Instant::now(), SystemTime::now());
+        let expiry = now + Duration::from_secs(100);
+        let millis = expiry_to_unix_millis(Some(expiry), now, system_now);
+        assert_eq!(expiry_to_unix_millis(None, now, system_now), NO_EXPIRY);
+
+        // Loading immediately keeps the same expiry (to the millisecond).
+        let remaining = time_until_unix_millis(millis, system_now).unwrap();
+        assert!(remaining > Duration::from_millis(99_990));
+
+        // Time spent offline is deducted, and IDs which expired offline are dropped.
+        let later = system_now + Duration::from_secs(80);
+        let remaining = time_until_unix_millis(millis, later).unwrap();
+        assert!(remaining <= Duration::from_secs(20));
+        let much_later = system_now + Duration::from_secs(101);
+        assert_eq!(time_until_unix_millis(millis, much_later), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let registry = ConcurrentIdRegistry::new();
+        let id = registry.claim_random();
+        let json = serde_json::to_string(&registry).unwrap();
+        assert_eq!(
+            json,
+            format!("[[{},null]]", serde_json::to_string(&id).unwrap())
+        );
+        let restored: ConcurrentIdRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(restored.contains(id));
+
+        let ttl = TinyId::random();
+        assert!(registry.claim_for(ttl, Duration::from_secs(30)));
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: ConcurrentIdRegistry = serde_json::from_str(&json).unwrap();
+        assert!(restored.contains(ttl));
+        assert!(restored.ids.get(&ttl).unwrap().is_some());
+
+        let json = format!("[[{},1]]", serde_json::to_string(&ttl).unwrap());
+        let restored: ConcurrentIdRegistry = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_empty());
+
+        for invalid in [TinyId::null(), TinyId::from_str_unchecked("abcdefg!")] {
+            let json = format!(
+                "[[{},null],[{},null]]",
+                serde_json::to_string(&id).unwrap(),
+                serde_json::to_string(&invalid).unwrap()
+            );
+            assert!(serde_json::from_str::<ConcurrentIdRegistry>(&json).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn threads() {
+        let registry = ConcurrentIdRegistry::new();
+        let id = TinyId::random();
+        let claimed = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        for _ in 0..1_000 {
+                            let _ = registry.claim_random();
+                        }
+                        registry.claim(id)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|h| h.join().ok())
+                .filter(|&won| won)
+                .count()
+        });
+        assert_eq!(claimed, 1);
+        assert_eq!(registry.len(), 8_001);
+    }
+}
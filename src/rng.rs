@@ -0,0 +1,217 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! The random number source behind every generated [`TinyId`](crate::TinyId).
+//!
+//! All generation draws from `fastrand`'s thread-local generator through the functions in this
+//! module, so that (with the `test-util` feature) it can be seeded from [`SEED_VAR`] before the
+//! first draw on each thread, and so that it can be reseeded explicitly with [`reseed_thread`].
+
+#[cfg(feature = "test-util")]
+use std::{cell::Cell, sync::OnceLock};
+
+#[cfg(feature = "test-util")]
+use crate::hash::{hash_bytes, mix64};
+
+/// The environment variable holding the seed for deterministic generation, see [`env_seed`].
+#[cfg(feature = "test-util")]
+pub const SEED_VAR: &str = "TINYID_SEED";
+
+/// Get the seed for deterministic generation from the [`SEED_VAR`] (`TINYID_SEED`) environment
+/// variable, or `None` if it is not set. The variable is only read once per process.
+///
+/// When a seed is set, each thread's generator is seeded from it (mixed with the thread's name)
+/// before the thread generates its first ID, so every thread that generates IDs, including
+/// [`TinyId::try_random`](crate::TinyId::try_random) with the `getrandom` feature, produces the
+/// same IDs on every run. The test harness names each test's thread after the test, so a flaky
+/// test can be re-run with the same seed to get identical IDs. Unnamed threads all share the
+/// same sequence.
+///
+/// ## Panics
+/// If `TINYID_SEED` is set but is not a valid `u64`.
+///
+/// ## Example
+/// ```
+/// // e.g. `TINYID_SEED=1234 cargo test`
+/// if let Some(seed) = tinyid::env_seed() {
+///     println!("generating IDs with {}={seed}", tinyid::SEED_VAR);
+/// }
+/// ```
+#[cfg(feature = "test-util")]
+#[must_use]
+pub fn env_seed() -> Option<u64> {
+    static SEED: OnceLock<Option<u64>> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let value = std::env::var(SEED_VAR).ok()?;
+        Some(
+            value
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("{SEED_VAR} must be a u64, not {value:?}")),
+        )
+    })
+}
+
+#[cfg(feature = "test-util")]
+thread_local! {
+    /// Whether this thread's generator has been seeded, from [`env_seed`] or [`reseed_thread`].
+    static SEEDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Seeds this thread's generator from [`env_seed`] the first time it is called on each thread.
+#[cfg(feature = "test-util")]
+fn ensure_seeded() {
+    if SEEDED.with(|seeded| seeded.replace(true)) {
+        return;
+    }
+    if let Some(seed) = env_seed() {
+        fastrand::seed(thread_seed(seed, std::thread::current().name()));
+    }
+}
+
+/// Derive the seed for a thread's generator from the [`env_seed`] and the thread's name.
+#[cfg(feature = "test-util")]
+fn thread_seed(seed: u64, name: Option<&str>) -> u64 {
+    mix64(seed ^ name.map_or(0, |name| hash_bytes(name.as_bytes())))
+}
+
+#[cfg(not(feature = "test-util"))]
+#[inline]
+fn ensure_seeded() {}
+
+/// Reseed the current thread's generator, which every random [`TinyId`](crate::TinyId) generated
+/// on this thread draws from. The same seed always gives the same sequence of IDs.
+///
+/// Generator state is per thread and lives in process memory, so after `fork()` (or when a
+/// sandbox is copy-on-write cloned) parent and child continue with identical state and would
+/// generate identical IDs. Reseed in the child, ideally from fresh entropy with
+/// [`entropy_from`], before generating any IDs. With the `test-util` feature, this also overrides
+/// any `TINYID_SEED` for this thread.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// tinyid::reseed_thread(1234);
+/// let a = TinyId::random();
+/// tinyid::reseed_thread(1234);
+/// assert_eq!(TinyId::random(), a);
+/// ```
+pub fn reseed_thread(seed: u64) {
+    #[cfg(feature = "test-util")]
+    SEEDED.with(|seeded| seeded.set(true));
+    fastrand::seed(seed);
+}
+
+/// Reseed the current thread's generator from an entropy source of the caller's choosing, e.g.
+/// the operating system's random number generator or a hardware RNG, see [`reseed_thread`].
+/// `fill` is called once with an 8 byte buffer to fill.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// // After `fork()`, give the child fresh state from the OS.
+/// tinyid::entropy_from(&mut |buf: &mut [u8]| {
+///     for (i, b) in buf.iter_mut().enumerate() {
+///         *b = i as u8; // e.g. `getrandom::getrandom(buf).unwrap()`
+///     }
+/// });
+/// assert!(TinyId::random().is_valid());
+/// ```
+pub fn entropy_from(fill: &mut impl FnMut(&mut [u8])) {
+    let mut bytes = [0u8; 8];
+    fill(&mut bytes);
+    reseed_thread(u64::from_le_bytes(bytes));
+}
+
+/// Checks whether generation is currently deterministic, i.e. a seed was given in `TINYID_SEED`.
+#[cfg_attr(not(feature = "getrandom"), allow(dead_code))]
+pub(crate) fn is_deterministic() -> bool {
+    #[cfg(feature = "test-util")]
+    {
+        env_seed().is_some()
+    }
+    #[cfg(not(feature = "test-util"))]
+    {
+        false
+    }
+}
+
+/// A random `u64`.
+pub(crate) fn u64() -> u64 {
+    ensure_seeded();
+    fastrand::u64(..)
+}
+
+/// A random `u64` in `0..n`.
+pub(crate) fn u64_below(n: u64) -> u64 {
+    ensure_seeded();
+    fastrand::u64(..n)
+}
+
+/// A random `usize` in `0..n`.
+pub(crate) fn usize_below(n: usize) -> usize {
+    ensure_seeded();
+    fastrand::usize(..n)
+}
+
+/// A random `u8` in `0..n`.
+pub(crate) fn u8_below(n: u8) -> u8 {
+    ensure_seeded();
+    fastrand::u8(..n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn reseed() {
+        let draw = || (0..10).map(|_| crate::TinyId::random()).collect::<Vec<_>>();
+        reseed_thread(7);
+        let a = draw();
+        reseed_thread(7);
+        assert_eq!(draw(), a);
+        reseed_thread(8);
+        assert_ne!(draw(), a);
+
+        let mut calls = 0;
+        let mut fill = |buf: &mut [u8]| {
+            calls += 1;
+            assert_eq!(buf.len(), 8);
+            buf.copy_from_slice(&7u64.to_le_bytes());
+        };
+        entropy_from(&mut fill);
+        assert_eq!(draw(), a);
+        assert_eq!(calls, 1);
+
+        // Another thread's state is untouched.
+        let other = std::thread::spawn(draw).join().unwrap();
+        assert_ne!(other, a);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn seeded_threads() {
+        let run = |name: &'static str| {
+            std::thread::spawn(move || {
+                ensure_seeded();
+                fastrand::seed(thread_seed(42, Some(name)));
+                (0..10).map(|_| crate::TinyId::random()).collect::<Vec<_>>()
+            })
+            .join()
+            .unwrap()
+        };
+        assert_eq!(run("a"), run("a"));
+        assert_ne!(run("a"), run("b"));
+        assert_ne!(thread_seed(42, None), thread_seed(43, None));
+        assert_ne!(thread_seed(42, None), thread_seed(42, Some("")));
+        assert_eq!(is_deterministic(), env_seed().is_some());
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Helpers for use with serde's `#[serde(with = "...")]` attribute, to customize how
+//! [`TinyId`] fields are (de)serialized.
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+use crate::{TinyId, Validation};
+
+/// Deserialize a [`TinyId`], validated according to `validation`.
+fn deserialize_with<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    validation: Validation,
+) -> Result<TinyId, D::Error> {
+    let id = TinyId::deserialize(deserializer)?;
+    validation
+        .check(id)
+        .map_err(|_| D::Error::custom(format!("invalid TinyId for {validation:?} validation")))
+}
+
+/// Deserialize a [`TinyId`], failing if it is the null ID. Serializes the same way as
+/// [`TinyId`] itself, for APIs where a null ID is always a bug.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "tinyid::serde_helpers::deny_null")]
+///     id: TinyId,
+/// }
+///
+/// let user = User { id: TinyId::null() };
+/// let json = serde_json::to_string(&user).unwrap();
+/// assert!(serde_json::from_str::<User>(&json).is_err());
+/// ```
+pub mod deny_null {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::TinyId;
+
+    /// Serialize `id` the same way as [`TinyId`] itself.
+    ///
+    /// ## Errors
+    /// Any error returned by the serializer.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        id.serialize(serializer)
+    }
+
+    /// Deserialize a [`TinyId`], failing if it is the null ID.
+    ///
+    /// ## Errors
+    /// Any error returned by the deserializer, or a custom error if the ID is null.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        let id = TinyId::deserialize(deserializer)?;
+        if id.is_null() {
+            return Err(D::Error::custom(
+                "expected a non-null TinyId, found the null ID",
+            ));
+        }
+        Ok(id)
+    }
+}
+
+/// Deserialize a [`TinyId`] with [`Validation::Strict`], rejecting the null ID and invalid
+/// characters. Serializes the same way as [`TinyId`] itself.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "tinyid::serde_helpers::strict")]
+///     id: TinyId,
+/// }
+///
+/// let json = r#"{"id":{"data":[97,98,99,100,101,102,103,33]}}"#;
+/// assert!(serde_json::from_str::<User>(json).is_err());
+/// ```
+pub mod strict {
+    use serde::{Deserializer, Serializer};
+
+    use crate::{TinyId, Validation};
+
+    /// Serialize `id` the same way as [`TinyId`] itself.
+    ///
+    /// ## Errors
+    /// Any error returned by the serializer.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        super::deny_null::serialize(id, serializer)
+    }
+
+    /// Deserialize a [`TinyId`] with [`Validation::Strict`].
+    ///
+    /// ## Errors
+    /// Any error returned by the deserializer, or a custom error if the ID is not valid.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        super::deserialize_with(deserializer, Validation::Strict)
+    }
+}
+
+/// Deserialize a [`TinyId`] with [`Validation::Lenient`], accepting the null ID but rejecting
+/// invalid characters. Serializes the same way as [`TinyId`] itself.
+pub mod lenient {
+    use serde::{Deserializer, Serializer};
+
+    use crate::{TinyId, Validation};
+
+    /// Serialize `id` the same way as [`TinyId`] itself.
+    ///
+    /// ## Errors
+    /// Any error returned by the serializer.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        super::deny_null::serialize(id, serializer)
+    }
+
+    /// Deserialize a [`TinyId`] with [`Validation::Lenient`].
+    ///
+    /// ## Errors
+    /// Any error returned by the deserializer, or a custom error if the ID is not valid.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        super::deserialize_with(deserializer, Validation::Lenient)
+    }
+}
+
+/// (De)serialize a [`TinyId`] as its 8-character string form, instead of the derived
+/// representation, for JSON APIs and to match the string schema documented by the `okapi` and
+/// `poem-openapi` features. Deserializing fails for invalid IDs (including the null ID).
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "tinyid::serde_helpers::string")]
+///     id: TinyId,
+/// }
+///
+/// let user = User { id: TinyId::from_str_unchecked("abcd1234") };
+/// assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"id":"abcd1234"}"#);
+/// assert!(serde_json::from_str::<User>(r#"{"id":"abcd123!"}"#).is_err());
+/// ```
+pub mod string {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    use crate::TinyId;
+
+    /// Serialize `id` as its display form.
+    ///
+    /// ## Errors
+    /// Any error returned by the serializer.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(id)
+    }
+
+    /// Deserialize a [`TinyId`] from its display form.
+    ///
+    /// ## Errors
+    /// Any error returned by the deserializer, or a custom error if the string is not a valid ID.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|err| D::Error::custom(format!("invalid TinyId {s:?}: {err}")))
+    }
+}
+
+/// (De)serialize a [`TinyId`] as a number that JavaScript can represent exactly, see
+/// [`TinyId::to_js_safe_number`], for JSON APIs whose clients expect numeric IDs. Serializing an
+/// invalid ID fails, since it has no such number.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     #[serde(with = "tinyid::serde_helpers::js_number")]
+///     id: TinyId,
+/// }
+///
+/// let user = User { id: TinyId::from_str_unchecked("------0-") };
+/// assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"id":64}"#);
+/// let user: User = serde_json::from_str(r#"{"id":64}"#).unwrap();
+/// assert_eq!(user.id, "------0-");
+/// ```
+pub mod js_number {
+    use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serializer};
+
+    use crate::TinyId;
+
+    /// Serialize `id` as its [`TinyId::to_js_safe_number`].
+    ///
+    /// ## Errors
+    /// Any error returned by the serializer, or a custom error if the ID is not valid.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        let n = id
+            .to_js_safe_number()
+            .ok_or_else(|| S::Error::custom("can not serialize an invalid TinyId as a number"))?;
+        serializer.serialize_u64(n)
+    }
+
+    /// Deserialize a [`TinyId`] from a number given by [`TinyId::to_js_safe_number`].
+    ///
+    /// ## Errors
+    /// Any error returned by the deserializer, or a custom error if the number is out of range.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        let n = u64::deserialize(deserializer)?;
+        TinyId::from_js_safe_number(n)
+            .ok_or_else(|| D::Error::custom(format!("{n} is out of range for a TinyId")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TinyId;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct DenyNull {
+        #[serde(with = "super::deny_null")]
+        id: TinyId,
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn deny_null() {
+        let value = DenyNull {
+            id: TinyId::random(),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            serde_json::to_value(&value).unwrap()["id"],
+            serde_json::to_value(value.id).unwrap()
+        );
+        assert_eq!(serde_json::from_str::<DenyNull>(&json).unwrap(), value);
+
+        let json = serde_json::to_string(&DenyNull { id: TinyId::null() }).unwrap();
+        let err = serde_json::from_str::<DenyNull>(&json).unwrap_err();
+        assert!(err.to_string().contains("null ID"), "{err}");
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Levels {
+        #[serde(with = "super::strict")]
+        strict: TinyId,
+        #[serde(with = "super::lenient")]
+        lenient: TinyId,
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn validation_levels() {
+        let value = Levels {
+            strict: TinyId::random(),
+            lenient: TinyId::null(),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<Levels>(&json).unwrap(), value);
+
+        let bad = serde_json::to_string(&TinyId::from_str_unchecked("abcdefg!")).unwrap();
+        let null = serde_json::to_string(&TinyId::null()).unwrap();
+        let good = serde_json::to_string(&TinyId::random()).unwrap();
+        let parse = |strict: &str, lenient: &str| {
+            serde_json::from_str::<Levels>(&format!(r#"{{"strict":{strict},"lenient":{lenient}}}"#))
+        };
+        assert!(parse(&null, &good).is_err());
+        assert!(parse(&bad, &good).is_err());
+        assert!(parse(&good, &bad).is_err());
+        assert!(parse(&good, &null).is_ok());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct AsString {
+        #[serde(with = "super::string")]
+        id: TinyId,
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn string() {
+        let value = AsString {
+            id: TinyId::random(),
+        };
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["id"], value.id.to_string());
+        assert_eq!(serde_json::from_value::<AsString>(json).unwrap(), value);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<AsString>(&json).unwrap(), value);
+
+        assert!(serde_json::from_str::<AsString>(r#"{"id":"abcdefghi"}"#).is_err());
+        assert!(serde_json::from_str::<AsString>(r#"{"id":12345678}"#).is_err());
+        let null = serde_json::to_string(&AsString { id: TinyId::null() }).unwrap();
+        assert!(serde_json::from_str::<AsString>(&null).is_err());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct JsNumber {
+        #[serde(with = "super::js_number")]
+        id: TinyId,
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn js_number() {
+        let value = JsNumber {
+            id: TinyId::random(),
+        };
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["id"].as_u64(), value.id.to_js_safe_number());
+        assert_eq!(serde_json::from_value::<JsNumber>(json).unwrap(), value);
+
+        assert!(serde_json::to_string(&JsNumber { id: TinyId::null() }).is_err());
+        assert!(serde_json::from_str::<JsNumber>(r#"{"id":281474976710656}"#).is_err());
+        assert!(serde_json::from_str::<JsNumber>(r#"{"id":-1}"#).is_err());
+    }
+}
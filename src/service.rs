@@ -0,0 +1,181 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{IdProvider, TinyId, TinyIdError};
+
+/// The number of pending requests an [`IdService`] buffers before callers have to wait.
+const DEFAULT_BUFFER: usize = 64;
+
+/// A single authoritative generator running as its own task, handing out IDs to any number of
+/// cloneable [`IdHandle`]s. Because every ID comes from the one provider, a uniqueness-tracking
+/// provider (e.g. [`UniqueGenerator`](crate::UniqueGenerator)) is the single source of truth for
+/// every task in the application.
+///
+/// The service stops once every handle has been dropped.
+///
+/// ## Example
+/// ```
+/// use tinyid::{IdService, UniqueGenerator};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let handle = IdService::spawn(UniqueGenerator::with_capacity(1_000));
+/// let other = handle.clone();
+/// let a = handle.request_id().await.unwrap();
+/// let b = other.request_id().await.unwrap();
+/// assert_ne!(a, b);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct IdService<P> {
+    provider: P,
+    sender: mpsc::Sender<oneshot::Sender<TinyId>>,
+    receiver: mpsc::Receiver<oneshot::Sender<TinyId>>,
+}
+
+/// A cloneable handle for requesting IDs from an [`IdService`].
+#[derive(Clone, Debug)]
+pub struct IdHandle {
+    sender: mpsc::Sender<oneshot::Sender<TinyId>>,
+}
+
+impl<P: IdProvider> IdService<P> {
+    /// Create a new [`IdService`] generating IDs with `provider`. The service does nothing until
+    /// [`IdService::run`] is awaited, see [`IdService::spawn`] to do both at once.
+    #[must_use]
+    pub fn new(provider: P) -> Self {
+        Self::with_buffer(provider, DEFAULT_BUFFER)
+    }
+
+    /// Create a new [`IdService`] which buffers up to `buffer` pending requests.
+    ///
+    /// ## Panics
+    /// If `buffer` is zero.
+    #[must_use]
+    pub fn with_buffer(provider: P, buffer: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(buffer);
+        Self {
+            provider,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Get a new handle for requesting IDs from this service.
+    #[must_use]
+    pub fn handle(&self) -> IdHandle {
+        IdHandle {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Serve requests until every handle has been dropped, then return the provider.
+    pub async fn run(self) -> P {
+        let Self {
+            mut provider,
+            sender,
+            mut receiver,
+        } = self;
+        drop(sender);
+        while let Some(reply) = receiver.recv().await {
+            // The requester may have given up waiting, in which case the ID is simply discarded.
+            let _ = reply.send(provider.next_id());
+        }
+        provider
+    }
+}
+
+impl<P: IdProvider + Send + 'static> IdService<P> {
+    /// Spawn a new service generating IDs with `provider` onto the current Tokio runtime,
+    /// returning a handle to it.
+    ///
+    /// ## Panics
+    /// If called outside of a Tokio runtime.
+    #[must_use]
+    pub fn spawn(provider: P) -> IdHandle {
+        let service = Self::new(provider);
+        let handle = service.handle();
+        tokio::spawn(service.run());
+        handle
+    }
+}
+
+impl IdHandle {
+    /// Request a new ID from the service.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the service is no longer running (e.g. its task was
+    ///   aborted, or its provider panicked).
+    pub async fn request_id(&self) -> Result<TinyId, TinyIdError> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(reply)
+            .await
+            .map_err(|_| TinyIdError::GenerationFailure)?;
+        response.await.map_err(|_| TinyIdError::GenerationFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SequentialProvider, UniqueGenerator};
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn many_tasks() {
+        let handle = IdService::spawn(UniqueGenerator::with_capacity(10_000));
+        let tasks = (0..10)
+            .map(|_| {
+                let handle = handle.clone();
+                tokio::spawn(async move {
+                    let mut ids = Vec::new();
+                    for _ in 0..100 {
+                        ids.push(handle.request_id().await.unwrap());
+                    }
+                    ids
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut all = std::collections::HashSet::new();
+        for task in tasks {
+            all.extend(task.await.unwrap());
+        }
+        assert_eq!(all.len(), 1_000);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn run_and_stop() {
+        let service = IdService::with_buffer(SequentialProvider::new(), 1);
+        let handle = service.handle();
+        let task = tokio::spawn(service.run());
+        assert_eq!(
+            handle.request_id().await,
+            Ok(TinyId::from_index_wrapping(0))
+        );
+        assert_eq!(
+            handle.request_id().await,
+            Ok(TinyId::from_index_wrapping(1))
+        );
+        drop(handle);
+        let mut provider = task.await.unwrap();
+        assert_eq!(provider.next_id(), TinyId::from_index_wrapping(2));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(coverage, no_coverage)]
+    async fn stopped() {
+        let service = IdService::new(SequentialProvider::new());
+        let handle = service.handle();
+        drop(service);
+        assert_eq!(
+            handle.request_id().await,
+            Err(TinyIdError::GenerationFailure)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::Arc;
+
+use crate::unique::Shard;
+use crate::{Generator, IdProvider, TinyId, TinyIdError};
+
+/// A [`Generator`] with its own RNG state, which can be shared between threads so that web
+/// frameworks and other multi-threaded services can keep a single generator in their application
+/// state instead of relying on thread-locals.
+///
+/// ## Thread Safety
+/// [`SharedGenerator`] is `Send + Sync` and every method takes `&self`, so it can be shared by
+/// reference or in an [`Arc`] (see [`Generator::shared`]) without a
+/// [`Mutex`](std::sync::Mutex). Its RNG state is split into cache-line padded shards, one per
+/// thread (round-robin), each of which is advanced with a single atomic `fetch_add`, so
+/// generating IDs never blocks and threads rarely contend. Cloning the [`Arc`] is just a reference
+/// count increment.
+///
+/// Unlike [`UniqueGenerator`](crate::UniqueGenerator), issued IDs are not tracked, so there is no
+/// capacity limit and no uniqueness guarantee beyond that of [`TinyId::random`].
+///
+/// ## Example
+/// ```
+/// use std::sync::Arc;
+/// use tinyid::{Generator, SharedGenerator};
+///
+/// let gen: Arc<SharedGenerator> = Generator::new().block("acme").shared();
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let gen = Arc::clone(&gen);
+///         std::thread::spawn(move || gen.next_id())
+///     })
+///     .collect();
+/// for handle in handles {
+///     let id = handle.join().unwrap();
+///     assert!(id.is_valid() && gen.generator().accepts(id));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SharedGenerator {
+    generator: Generator,
+    shards: Box<[Shard]>,
+}
+
+impl SharedGenerator {
+    /// Create a new [`SharedGenerator`] with no options set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_generator(Generator::new())
+    }
+
+    /// Create a new [`SharedGenerator`] generating IDs according to the options of `generator`.
+    #[must_use]
+    pub fn from_generator(generator: Generator) -> Self {
+        Self {
+            generator,
+            shards: Shard::new_set(),
+        }
+    }
+
+    /// The options this generator was created with.
+    #[must_use]
+    pub fn generator(&self) -> &Generator {
+        &self.generator
+    }
+
+    /// Generate a new random [`TinyId`] satisfying this generator's options.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if no acceptable ID was found after
+    ///   [`TinyId::RANDOM_WHERE_ATTEMPTS`] attempts, which only happens if the options reject
+    ///   (nearly) every ID.
+    pub fn try_next_id(&self) -> Result<TinyId, TinyIdError> {
+        (0..TinyId::RANDOM_WHERE_ATTEMPTS)
+            .map(|_| TinyId::from_random_bits(Shard::next_bits(&self.shards)))
+            .find(|&id| self.generator.accepts(id))
+            .ok_or(TinyIdError::GenerationFailure)
+    }
+
+    /// Generate a new random [`TinyId`] satisfying this generator's options.
+    ///
+    /// ## Panics
+    /// If the options reject (nearly) every ID, see [`SharedGenerator::try_next_id`].
+    #[must_use]
+    pub fn next_id(&self) -> TinyId {
+        self.try_next_id()
+            .expect("Generator options reject (nearly) every ID")
+    }
+}
+
+impl Default for SharedGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Generator> for SharedGenerator {
+    fn from(generator: Generator) -> Self {
+        Self::from_generator(generator)
+    }
+}
+
+impl IdProvider for SharedGenerator {
+    fn next_id(&mut self) -> TinyId {
+        SharedGenerator::next_id(self)
+    }
+}
+
+impl IdProvider for &SharedGenerator {
+    fn next_id(&mut self) -> TinyId {
+        SharedGenerator::next_id(self)
+    }
+}
+
+impl Generator {
+    /// Turn this generator into a [`SharedGenerator`] in an [`Arc`], ready to be cloned into
+    /// application state or handed to other threads.
+    #[must_use]
+    pub fn shared(self) -> Arc<SharedGenerator> {
+        Arc::new(SharedGenerator::from_generator(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedGenerator>();
+        assert_send_sync::<Arc<SharedGenerator>>();
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn generating() {
+        use std::collections::HashSet;
+
+        let gen = Generator::new().block_all(["a", "b", "c"]).shared();
+        let ids: HashSet<TinyId> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| s.spawn(|| (0..1_000).map(|_| gen.next_id()).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+        assert_eq!(ids.len(), 8 * 1_000);
+        assert!(ids
+            .iter()
+            .all(|&id| id.is_valid() && !gen.generator().is_blocked(id)));
+
+        let mut provider = &*gen;
+        assert!(IdProvider::next_id(&mut provider).is_valid());
+        assert!(SharedGenerator::default().next_id().is_valid());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn rejects_everything() {
+        let everything = TinyId::LETTERS
+            .iter()
+            .map(|&b| (b as char).to_string())
+            .collect::<Vec<_>>();
+        let gen = SharedGenerator::from(Generator::new().block_all(&everything));
+        assert_eq!(gen.try_next_id(), Err(TinyIdError::GenerationFailure));
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::TinyId;
+
+/// The NATO phonetic alphabet, `alfa` to `zulu`.
+const NATO: [&str; 26] = [
+    "alfa", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliett",
+    "kilo", "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango",
+    "uniform", "victor", "whiskey", "xray", "yankee", "zulu",
+];
+
+/// The names of the digits `0` to `9`.
+const DIGITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Spell out a single character of an ID.
+fn spell(byte: u8) -> String {
+    match byte {
+        b'a'..=b'z' => NATO[usize::from(byte - b'a')].to_string(),
+        b'A'..=b'Z' => format!(
+            "{} upper",
+            NATO[usize::from(byte - b'A')].to_ascii_uppercase()
+        ),
+        b'0'..=b'9' => DIGITS[usize::from(byte - b'0')].to_string(),
+        b'-' => "dash".to_string(),
+        b'_' => "underscore".to_string(),
+        _ => "invalid".to_string(),
+    }
+}
+
+impl TinyId {
+    /// Spell out this [`TinyId`] one character at a time for reading aloud, e.g. by support teams
+    /// dictating IDs over the phone. Letters use the NATO phonetic alphabet, with uppercase
+    /// letters written in capitals and followed by `upper`; digits use their names, and `-` and
+    /// `_` are `dash` and `underscore`. Any invalid byte (e.g. in the null ID) is `invalid`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("Ab3-_xYz");
+    /// assert_eq!(
+    ///     id.spell_out(),
+    ///     "ALFA upper, bravo, three, dash, underscore, xray, YANKEE upper, zulu"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn spell_out(&self) -> String {
+        self.data
+            .iter()
+            .map(|&b| spell(b))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn every_letter() {
+        let mut words = TinyId::LETTERS.map(spell).to_vec();
+        assert!(words.iter().all(|word| word != "invalid"));
+        words.sort();
+        words.dedup();
+        assert_eq!(words.len(), TinyId::LETTER_COUNT);
+        assert_eq!(spell(b'Q'), "QUEBEC upper");
+        assert_eq!(spell(b'q'), "quebec");
+        assert_eq!(spell(b'9'), "nine");
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn spell_out() {
+        for _ in 0..100 {
+            assert_eq!(TinyId::random().spell_out().split(", ").count(), 8);
+        }
+        assert_eq!(
+            TinyId::from_str_unchecked("0000zzzz").spell_out(),
+            "zero, zero, zero, zero, zulu, zulu, zulu, zulu"
+        );
+        assert_eq!(TinyId::null().spell_out(), ["invalid"; 8].join(", "));
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::TinyId;
+
+/// A fixed-capacity set of claimed [`TinyId`]s, stored inline in a sorted array so it never
+/// allocates. Suitable for code which must not touch the heap after startup, including as a
+/// `static` since [`StaticIdRegistry::new`] is `const`.
+///
+/// This type does not make the crate usable without `std`: like the rest of the crate,
+/// [`StaticIdRegistry::claim_random`] draws from `fastrand`'s thread-local random number
+/// generator.
+///
+/// Like [`heapless`](https://crates.io/crates/heapless)'s collections, claiming an ID when the
+/// registry is full hands it back as the error.
+///
+/// ## Example
+/// ```
+/// use tinyid::{StaticIdRegistry, TinyId};
+///
+/// let mut sessions = StaticIdRegistry::<2>::new();
+/// let a = TinyId::random();
+/// assert_eq!(sessions.claim(a), Ok(true));
+/// assert_eq!(sessions.claim(a), Ok(false));
+/// assert!(sessions.contains(a));
+///
+/// let b = sessions.claim_random().unwrap();
+/// assert!(sessions.is_full());
+/// let c = TinyId::random();
+/// assert_eq!(sessions.claim(c), Err(c));
+///
+/// assert!(sessions.release(b));
+/// assert_eq!(sessions.claim(c), Ok(true));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StaticIdRegistry<const CAP: usize> {
+    /// The claimed IDs are `ids[..len]`, kept sorted.
+    ids: [TinyId; CAP],
+    len: usize,
+}
+
+impl<const CAP: usize> StaticIdRegistry<CAP> {
+    /// The number of IDs this registry can hold.
+    pub const CAPACITY: usize = CAP;
+
+    /// Create a new, empty [`StaticIdRegistry`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ids: [TinyId {
+                data: TinyId::NULL_DATA,
+            }; CAP],
+            len: 0,
+        }
+    }
+
+    /// Claims the given ID, returning `Ok(true)` if it was not already claimed and `Ok(false)` if
+    /// it was.
+    ///
+    /// ## Errors
+    /// Returns `id` back if it was not already claimed and the registry is full.
+    pub fn claim(&mut self, id: TinyId) -> Result<bool, TinyId> {
+        match self.position(id) {
+            Ok(_) => Ok(false),
+            Err(_) if self.is_full() => Err(id),
+            Err(index) => {
+                self.ids.copy_within(index..self.len, index + 1);
+                self.ids[index] = id;
+                self.len += 1;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Generates and claims a new random ID which was not already claimed, or returns `None`
+    /// if the registry is full.
+    pub fn claim_random(&mut self) -> Option<TinyId> {
+        if self.is_full() {
+            return None;
+        }
+        loop {
+            let id = TinyId::random();
+            if self.claim(id) == Ok(true) {
+                return Some(id);
+            }
+        }
+    }
+
+    /// Releases the given ID, returning `true` if it was claimed.
+    pub fn release(&mut self, id: TinyId) -> bool {
+        let Ok(index) = self.position(id) else {
+            return false;
+        };
+        self.ids.copy_within(index + 1..self.len, index);
+        self.len -= 1;
+        true
+    }
+
+    /// Checks whether the given ID is currently claimed.
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        self.position(id).is_ok()
+    }
+
+    /// The number of claimed IDs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether no IDs are claimed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Checks whether the registry has no room for any more IDs.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    /// Releases every claimed ID.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// The claimed IDs, in sorted order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[TinyId] {
+        &self.ids[..self.len]
+    }
+
+    /// Iterate over the claimed IDs, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = TinyId> + '_ {
+        self.as_slice().iter().copied()
+    }
+
+    /// Find `id` among the claimed IDs, or where it would be inserted.
+    fn position(&self, id: TinyId) -> Result<usize, usize> {
+        self.as_slice().binary_search(&id)
+    }
+}
+
+impl<const CAP: usize> Default for StaticIdRegistry<CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn claim_release() {
+        let mut registry = StaticIdRegistry::<16>::new();
+        assert!(registry.is_empty());
+        assert_eq!(StaticIdRegistry::<16>::CAPACITY, 16);
+        let ids = (0..16)
+            .map(|_| registry.claim_random().unwrap())
+            .collect::<Vec<_>>();
+        assert!(registry.is_full());
+        assert_eq!(registry.len(), 16);
+        assert_eq!(registry.claim_random(), None);
+        assert!(registry.as_slice().windows(2).all(|w| w[0] < w[1]));
+        for &id in &ids {
+            assert!(registry.contains(id));
+            assert_eq!(registry.claim(id), Ok(false));
+        }
+        let extra = TinyId::random();
+        assert_eq!(registry.claim(extra), Err(extra));
+
+        for &id in ids.iter().step_by(2) {
+            assert!(registry.release(id));
+            assert!(!registry.release(id));
+            assert!(!registry.contains(id));
+        }
+        assert_eq!(registry.len(), 8);
+        assert!(registry.iter().eq(ids
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>()));
+        assert_eq!(registry.claim(extra), Ok(true));
+        assert!(registry.as_slice().windows(2).all(|w| w[0] < w[1]));
+
+        registry.clear();
+        assert!(registry.is_empty());
+        assert!(!registry.contains(extra));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn zero_capacity() {
+        let mut registry = StaticIdRegistry::<0>::default();
+        assert!(registry.is_full());
+        assert_eq!(registry.claim_random(), None);
+        let id = TinyId::random();
+        assert_eq!(registry.claim(id), Err(id));
+        assert!(!registry.release(id));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn in_static() {
+        static REGISTRY: std::sync::Mutex<StaticIdRegistry<4>> =
+            std::sync::Mutex::new(StaticIdRegistry::new());
+        let id = REGISTRY.lock().unwrap().claim_random().unwrap();
+        assert!(REGISTRY.lock().unwrap().contains(id));
+    }
+}
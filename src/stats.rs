@@ -0,0 +1,92 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A snapshot of the activity of a [`UniqueGenerator`](crate::UniqueGenerator) or
+/// `ConcurrentIdRegistry`, for monitoring how full it is getting.
+///
+/// Counters start at zero when the generator or registry is created (or loaded) and are never
+/// saved.
+///
+/// ## Metrics
+/// With the `metrics` feature, the same activity is also reported to the global
+/// [`metrics`](https://docs.rs/metrics) recorder (e.g. a Prometheus exporter) as it happens, under
+/// the names in the `METRIC_*` constants.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GenerationStats {
+    /// The number of IDs successfully issued (or claimed).
+    pub issued: u64,
+    /// The number of times an ID was rejected because it was already issued (or claimed).
+    pub collisions: u64,
+    /// The number of times a random candidate had to be regenerated after a collision.
+    pub retries: u64,
+    /// How full the generator or registry is, from `0.0` (empty) to `1.0` (full).
+    pub occupancy: f64,
+}
+
+#[cfg(feature = "metrics")]
+impl GenerationStats {
+    /// The counter of IDs issued (or claimed), see [`GenerationStats::issued`].
+    pub const METRIC_IDS_GENERATED: &'static str = "tinyid_ids_generated_total";
+    /// The counter of random candidates regenerated after a collision, see
+    /// [`GenerationStats::retries`].
+    pub const METRIC_COLLISION_RETRIES: &'static str = "tinyid_collision_retries_total";
+    /// The histogram of how long each random ID took to generate, in seconds, including any
+    /// retries.
+    pub const METRIC_GENERATION_DURATION: &'static str = "tinyid_generation_duration_seconds";
+}
+
+/// The live counters behind [`GenerationStats`], updated with relaxed atomics so that recording
+/// them never slows down generation.
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    issued: AtomicU64,
+    collisions: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl Counters {
+    /// Record one successfully issued ID.
+    pub(crate) fn issued(&self) {
+        self.issued.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!(GenerationStats::METRIC_IDS_GENERATED).increment(1);
+    }
+
+    /// Record one collision.
+    pub(crate) fn collision(&self) {
+        self.collisions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one retry.
+    pub(crate) fn retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!(GenerationStats::METRIC_COLLISION_RETRIES).increment(1);
+    }
+
+    /// Run `generate`, recording how long it took in the generation duration histogram.
+    #[allow(clippy::unused_self)]
+    pub(crate) fn time<T>(&self, generate: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+        let result = generate();
+        #[cfg(feature = "metrics")]
+        metrics::histogram!(GenerationStats::METRIC_GENERATION_DURATION).record(start.elapsed());
+        result
+    }
+
+    /// Take a snapshot of the counters, along with the given occupancy.
+    pub(crate) fn snapshot(&self, occupancy: f64) -> GenerationStats {
+        GenerationStats {
+            issued: self.issued.load(Ordering::Relaxed),
+            collisions: self.collisions.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            occupancy,
+        }
+    }
+}
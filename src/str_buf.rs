@@ -0,0 +1,155 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::ops::Deref;
+
+use crate::TinyId;
+
+/// The owned string form of a [`TinyId`], stored inline in 8 bytes so creating one never
+/// allocates. Dereferences to [`str`].
+///
+/// Valid (and null) IDs are always ASCII; any other byte in an ID created without validation is
+/// replaced with `?`, so the string is always exactly 8 bytes long.
+///
+/// ## Example
+/// ```
+/// use tinyid::TinyId;
+///
+/// let id = TinyId::from_str_unchecked("abcdefgh");
+/// let s = id.to_str_buf();
+/// assert_eq!(&*s, "abcdefgh");
+/// assert_eq!(s.to_uppercase(), "ABCDEFGH");
+/// assert_eq!(format!("key:{s}"), "key:abcdefgh");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TinyIdStr {
+    /// Always ASCII.
+    bytes: [u8; 8],
+}
+
+impl TinyIdStr {
+    /// Borrow this string as a [`str`].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Always ASCII, so this never falls back to the default.
+        std::str::from_utf8(&self.bytes).unwrap_or_default()
+    }
+}
+
+impl TinyId {
+    /// Get the string form of this [`TinyId`] without allocating, see [`TinyIdStr`].
+    #[must_use]
+    pub fn to_str_buf(self) -> TinyIdStr {
+        TinyIdStr {
+            bytes: self.data.map(|b| if b.is_ascii() { b } else { b'?' }),
+        }
+    }
+}
+
+impl From<TinyId> for TinyIdStr {
+    fn from(id: TinyId) -> Self {
+        id.to_str_buf()
+    }
+}
+
+impl Deref for TinyIdStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for TinyIdStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for TinyIdStr {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl std::borrow::Borrow<str> for TinyIdStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Hashes the same as the [`str`] it borrows as, as required by [`Borrow`](std::borrow::Borrow).
+impl std::hash::Hash for TinyIdStr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl std::fmt::Debug for TinyIdStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl std::fmt::Display for TinyIdStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for TinyIdStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for TinyIdStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn matches_to_string() {
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            let s = id.to_str_buf();
+            assert_eq!(s, id.to_string().as_str());
+            assert_eq!(s.len(), 8);
+            assert_eq!(s.to_string(), id.to_string());
+            assert_eq!(format!("{s:?}"), format!("{:?}", id.to_string()));
+            assert_eq!(TinyIdStr::from(id), s);
+        }
+        assert_eq!(TinyId::null().to_str_buf(), "\0\0\0\0\0\0\0\0");
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn replaces_non_ascii() {
+        let id = TinyId::from_bytes_unchecked([b'a', 0xFF, b'b', 0xC3, 0xA9, b'c', b'd', b'e']);
+        let s = id.to_str_buf();
+        assert_eq!(s, "a?b??cde");
+        let bytes: &[u8] = s.as_ref();
+        assert_eq!(bytes, b"a?b??cde");
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn borrow_as_str() {
+        let id = TinyId::random();
+        let mut set = std::collections::HashSet::new();
+        set.insert(id.to_str_buf());
+        assert!(set.contains(id.to_string().as_str()));
+        let buf = id.to_str_buf();
+        let s: &str = buf.as_ref();
+        assert_eq!(s, id.to_string());
+    }
+}
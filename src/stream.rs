@@ -0,0 +1,116 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{IdProvider, RandomProvider, TinyId};
+
+/// An endless [`Stream`] of [`TinyId`]s drawn from an [`IdProvider`]. Generating an ID never
+/// blocks, so every poll is immediately ready.
+///
+/// Created with [`id_stream`] for random IDs, or [`IdStream::new`] for any provider, e.g. a
+/// [`UniqueGenerator`](crate::UniqueGenerator) for a stream without repeats.
+///
+/// ## Example
+/// ```
+/// use futures::StreamExt;
+/// use tinyid::{IdStream, UniqueGenerator};
+///
+/// futures::executor::block_on(async {
+///     let mut stream = IdStream::new(UniqueGenerator::with_capacity(100)).take(3);
+///     while let Some(id) = stream.next().await {
+///         assert!(id.is_valid());
+///     }
+/// });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IdStream<P = RandomProvider> {
+    provider: P,
+}
+
+/// Create an endless [`Stream`] of random [`TinyId`]s.
+///
+/// ## Example
+/// ```
+/// use futures::StreamExt;
+///
+/// let ids = futures::executor::block_on(tinyid::id_stream().take(5).collect::<Vec<_>>());
+/// assert_eq!(ids.len(), 5);
+/// ```
+#[must_use]
+pub fn id_stream() -> IdStream<RandomProvider> {
+    IdStream::new(RandomProvider)
+}
+
+impl<P: IdProvider> IdStream<P> {
+    /// Create a new [`IdStream`] drawing IDs from `provider`.
+    #[must_use]
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Get a reference to the underlying provider.
+    #[must_use]
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Consume this stream, returning the underlying provider.
+    #[must_use]
+    pub fn into_inner(self) -> P {
+        self.provider
+    }
+}
+
+impl<P: IdProvider + Unpin> Stream for IdStream<P> {
+    type Item = TinyId;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(Some(self.get_mut().provider.next_id()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, StreamExt};
+
+    use super::*;
+    use crate::SequentialProvider;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn random() {
+        let ids = block_on(
+            id_stream()
+                .take(100)
+                .collect::<std::collections::HashSet<_>>(),
+        );
+        assert_eq!(ids.len(), 100);
+        assert!(ids.iter().all(|id| id.is_valid()));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn provider() {
+        let mut stream = IdStream::new(SequentialProvider::new());
+        let ids = block_on(stream.by_ref().take(3).collect::<Vec<_>>());
+        assert_eq!(
+            ids,
+            (0..3).map(TinyId::from_index_wrapping).collect::<Vec<_>>()
+        );
+        let mut provider = stream.into_inner();
+        assert_eq!(provider.next_id(), TinyId::from_index_wrapping(3));
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// A [`TinyId`] wrapper which (de)serializes as its display form (`"abcd1234"`) instead of as its
+/// raw bytes, like a field using [`serde_helpers::string`](crate::serde_helpers::string).
+/// Deserializing validates the ID. It dereferences to the wrapped ID.
+///
+/// With the `okapi` feature it documents itself as an 8-character string in `OpenAPI` schemas,
+/// so it should be used for IDs in JSON bodies of `rocket_okapi` routes.
+///
+/// ## Example
+/// ```
+/// use tinyid::{StringTinyId, TinyId};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct User {
+///     id: StringTinyId,
+/// }
+///
+/// let user = User { id: TinyId::from_str_unchecked("abcd1234").into() };
+/// assert_eq!(serde_json::to_string(&user).unwrap(), r#"{"id":"abcd1234"}"#);
+/// assert!(serde_json::from_str::<User>(r#"{"id":"abcd123!"}"#).is_err());
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(transparent)]
+pub struct StringTinyId(#[serde(with = "crate::serde_helpers::string")] pub TinyId);
+
+impl std::ops::Deref for StringTinyId {
+    type Target = TinyId;
+
+    fn deref(&self) -> &TinyId {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for StringTinyId {
+    fn deref_mut(&mut self) -> &mut TinyId {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Display for StringTinyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::str::FromStr for StringTinyId {
+    type Err = TinyIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl From<TinyId> for StringTinyId {
+    fn from(id: TinyId) -> Self {
+        Self(id)
+    }
+}
+
+impl From<StringTinyId> for TinyId {
+    fn from(wrapper: StringTinyId) -> Self {
+        wrapper.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde_as_string() {
+        let id = StringTinyId::from(TinyId::random());
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(json, serde_json::Value::String(id.to_string()));
+        assert_eq!(serde_json::from_value::<StringTinyId>(json).unwrap(), id);
+        assert!(serde_json::from_str::<StringTinyId>(r#""abc""#).is_err());
+        assert_eq!(id.to_string().parse(), Ok(id));
+        assert_eq!(TinyId::from(id), id.0);
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::TinyId;
+
+/// The Levenshtein distance between two byte strings, i.e. the number of single byte
+/// insertions, deletions and substitutions needed to turn `a` into `b`.
+pub(crate) fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+impl TinyId {
+    /// Find the `candidates` within `max_distance` edits of `input`, closest first. Useful for
+    /// responding to a mistyped ID with "did you mean ...?". Candidates at the same distance keep
+    /// their original order.
+    ///
+    /// Distance is the Levenshtein edit distance between `input` and the candidate's display form.
+    /// Comparison is case-sensitive, as IDs differing only in case are distinct.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let known = [
+    ///     TinyId::from_str_unchecked("aBc9-x1_"),
+    ///     TinyId::from_str_unchecked("zzzzzzzz"),
+    /// ];
+    /// let suggestions = TinyId::suggest("aBc9x1_", known, 2);
+    /// assert_eq!(suggestions, vec![known[0]]);
+    /// ```
+    #[must_use]
+    pub fn suggest(
+        input: &str,
+        candidates: impl IntoIterator<Item = TinyId>,
+        max_distance: usize,
+    ) -> Vec<TinyId> {
+        let mut found = candidates
+            .into_iter()
+            .map(|id| (edit_distance(input.as_bytes(), &id.data), id))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect::<Vec<_>>();
+        found.sort_by_key(|(distance, _)| *distance);
+        found.into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn distance() {
+        assert_eq!(edit_distance(b"", b""), 0);
+        assert_eq!(edit_distance(b"abc", b""), 3);
+        assert_eq!(edit_distance(b"", b"abc"), 3);
+        assert_eq!(edit_distance(b"abcdefgh", b"abcdefgh"), 0);
+        assert_eq!(edit_distance(b"abcdefgh", b"abcdxfgh"), 1);
+        assert_eq!(edit_distance(b"abcdefgh", b"abcdfgh"), 1);
+        assert_eq!(edit_distance(b"abcdefgh", b"abcdeefgh"), 1);
+        assert_eq!(edit_distance(b"abcdefgh", b"bacdefgh"), 2);
+        assert_eq!(edit_distance(b"kitten", b"sitting"), 3);
+        assert_eq!(edit_distance(b"abcdefgh", b"ABCDEFGH"), 8);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn suggest() {
+        let a = TinyId::from_str_unchecked("abcdefgh");
+        let b = TinyId::from_str_unchecked("abcdefgX");
+        let c = TinyId::from_str_unchecked("abcdeXYZ");
+        let d = TinyId::from_str_unchecked("12345678");
+        let all = [d, c, b, a];
+
+        assert_eq!(TinyId::suggest("abcdefgh", all, 0), vec![a]);
+        assert_eq!(TinyId::suggest("abcdefgh", all, 1), vec![a, b]);
+        assert_eq!(TinyId::suggest("abcdefgh", all, 3), vec![a, b, c]);
+        assert_eq!(TinyId::suggest("abcdefg", all, 1), vec![b, a]);
+        assert_eq!(TinyId::suggest("abcdefgh", all, 8), vec![a, b, c, d]);
+        assert!(TinyId::suggest("abcdefgh", [], 8).is_empty());
+        assert!(TinyId::suggest("", all, 7).is_empty());
+    }
+}
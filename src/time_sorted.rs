@@ -0,0 +1,441 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use crate::{index::KEYSPACE, IdProvider, TinyId, TinyIdError};
+
+/// The number of letters used for the timestamp by default, enough for 139 years of
+/// milliseconds.
+const DEFAULT_TIMESTAMP_CHARS: u32 = 7;
+
+/// The number of bits each letter holds.
+const BITS_PER_CHAR: u32 = 6;
+
+/// The value of [`TimeSortedGenerator::last`] before any ID has been generated, which is past the
+/// end of the keyspace.
+const NO_LAST: u64 = u64::MAX;
+
+/// A generator of [`TinyId`]s which sort in the order they were created.
+///
+/// The leading letters hold the number of ticks (1ms by default) since an epoch, and the rest
+/// are random. Since an ID only holds 48 bits, there is a tradeoff between how long the
+/// generator can be used for and how many IDs can be created per tick without colliding:
+/// - A recent custom epoch ([`TimeSortedGenerator::with_epoch`], e.g. the company founding
+///   date) and a coarser tick ([`TimeSortedGenerator::with_tick`]) mean the timestamp fits in
+///   fewer letters ([`TimeSortedGenerator::with_timestamp_chars`]), leaving more for
+///   randomness.
+/// - By default the epoch is the Unix epoch, ticks are milliseconds, and 7 letters are used,
+///   lasting until 2109 but leaving only 64 possible IDs per millisecond before they carry into
+///   the next one.
+///
+/// ## Monotonicity
+/// Like ULIDs, IDs from [`TimeSortedGenerator::next_id`] are strictly increasing, even within
+/// a single tick (or if the clock goes backwards): when the tick has not advanced, the previous
+/// ID is incremented instead of drawing new random letters. When a tick runs out of IDs the count
+/// carries into the next tick, so a burst of IDs may be timestamped slightly ahead of the clock.
+/// So IDs from one generator never collide, and generation only fails once the timestamp runs
+/// out (see [`TimeSortedGenerator::latest`]).
+///
+/// ## Example
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use tinyid::TimeSortedGenerator;
+///
+/// let founded = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+/// let gen = TimeSortedGenerator::new()
+///     .with_epoch(founded)
+///     .with_tick(Duration::from_secs(1))
+///     .with_timestamp_chars(5);
+/// let id = gen.next_id();
+/// assert!(gen.timestamp(id).unwrap() <= SystemTime::now());
+///
+/// let earlier = gen.try_id_at(founded + Duration::from_secs(60)).unwrap();
+/// let later = gen.try_id_at(founded + Duration::from_secs(61)).unwrap();
+/// assert!(earlier < later);
+/// ```
+#[derive(Debug)]
+pub struct TimeSortedGenerator {
+    epoch: SystemTime,
+    tick: Duration,
+    timestamp_chars: u32,
+    /// The index of the last ID from [`TimeSortedGenerator::next_id`], or [`NO_LAST`].
+    last: AtomicU64,
+}
+
+impl TimeSortedGenerator {
+    /// Create a new [`TimeSortedGenerator`] counting milliseconds since the Unix epoch in the
+    /// first 7 letters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            epoch: SystemTime::UNIX_EPOCH,
+            tick: Duration::from_millis(1),
+            timestamp_chars: DEFAULT_TIMESTAMP_CHARS,
+            last: AtomicU64::new(NO_LAST),
+        }
+    }
+
+    /// Count time from `epoch` instead of the Unix epoch. IDs cannot be generated for times
+    /// before the epoch.
+    #[must_use]
+    pub fn with_epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Count time in ticks of `tick` (clamped to at least 1ns) instead of milliseconds.
+    #[must_use]
+    pub fn with_tick(mut self, tick: Duration) -> Self {
+        self.tick = tick.max(Duration::from_nanos(1));
+        self
+    }
+
+    /// Use the first `chars` letters (clamped to `1..=8`) for the timestamp, and the rest for
+    /// randomness.
+    #[must_use]
+    pub fn with_timestamp_chars(mut self, chars: u32) -> Self {
+        self.timestamp_chars = chars.clamp(1, 8);
+        self
+    }
+
+    /// The time that timestamps are counted from.
+    #[must_use]
+    pub fn epoch(&self) -> SystemTime {
+        self.epoch
+    }
+
+    /// The length of one tick of the timestamp.
+    #[must_use]
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+
+    /// The number of letters holding the timestamp.
+    #[must_use]
+    pub fn timestamp_chars(&self) -> u32 {
+        self.timestamp_chars
+    }
+
+    /// The last time which fits in the timestamp; IDs cannot be generated after it.
+    #[must_use]
+    pub fn latest(&self) -> SystemTime {
+        self.time_of_tick((1 << self.timestamp_bits()) - 1)
+    }
+
+    /// Generate a new [`TinyId`] for the current time, greater than every ID this generator has
+    /// generated before (see [Monotonicity](#monotonicity)).
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the current time is before the epoch or after
+    ///   [`TimeSortedGenerator::latest`], or if every ID up to the end of the keyspace has been
+    ///   used.
+    pub fn try_next_id(&self) -> Result<TinyId, TinyIdError> {
+        self.try_next_id_at(SystemTime::now())
+    }
+
+    /// Generate a new [`TinyId`] for the current time, greater than every ID this generator has
+    /// generated before (see [Monotonicity](#monotonicity)).
+    ///
+    /// ## Panics
+    /// If the current time does not fit in the timestamp or the keyspace has run out, see
+    /// [`TimeSortedGenerator::try_next_id`].
+    #[must_use]
+    pub fn next_id(&self) -> TinyId {
+        self.try_next_id()
+            .expect("the current time does not fit in the timestamp, or the keyspace is exhausted")
+    }
+
+    /// Generate a new [`TinyId`] as if it were created at `time`. Unlike
+    /// [`TimeSortedGenerator::next_id`] this is not monotonic: IDs for the same tick are random,
+    /// and may collide.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if `time` is before the epoch or after
+    ///   [`TimeSortedGenerator::latest`].
+    pub fn try_id_at(&self, time: SystemTime) -> Result<TinyId, TinyIdError> {
+        let ticks = self.ticks_at(time).ok_or(TinyIdError::GenerationFailure)?;
+        let random = crate::rng::u64() & ((1 << self.random_bits()) - 1);
+        Ok(TinyId::from_index_wrapping(
+            ticks << self.random_bits() | random,
+        ))
+    }
+
+    /// Get the start of the tick in which `id` was created, or `None` if it is not valid.
+    #[must_use]
+    pub fn timestamp(&self, id: TinyId) -> Option<SystemTime> {
+        id.to_index()
+            .map(|index| self.time_of_tick(index >> self.random_bits()))
+    }
+
+    /// The monotonic part of [`TimeSortedGenerator::try_next_id`], with the current time given.
+    fn try_next_id_at(&self, time: SystemTime) -> Result<TinyId, TinyIdError> {
+        let ticks = self.ticks_at(time).ok_or(TinyIdError::GenerationFailure)?;
+        let random_bits = self.random_bits();
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let next = if last != NO_LAST && last >> random_bits >= ticks {
+                // Still in (or behind) the last ID's tick, so count up from it. A full tick
+                // carries into the next one rather than failing.
+                let next = last + 1;
+                if next >= KEYSPACE {
+                    return Err(TinyIdError::GenerationFailure);
+                }
+                next
+            } else {
+                ticks << random_bits | crate::rng::u64() & ((1 << random_bits) - 1)
+            };
+            match self
+                .last
+                .compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return Ok(TinyId::from_index_wrapping(next)),
+                Err(actual) => last = actual,
+            }
+        }
+    }
+
+    /// The number of bits holding the timestamp.
+    fn timestamp_bits(&self) -> u32 {
+        self.timestamp_chars * BITS_PER_CHAR
+    }
+
+    /// The number of bits holding randomness.
+    fn random_bits(&self) -> u32 {
+        KEYSPACE.trailing_zeros() - self.timestamp_bits()
+    }
+
+    /// The number of whole ticks from the epoch to `time`, if it fits in the timestamp.
+    fn ticks_at(&self, time: SystemTime) -> Option<u64> {
+        let elapsed = time.duration_since(self.epoch).ok()?;
+        let ticks = u64::try_from(elapsed.as_nanos() / self.tick.as_nanos()).ok()?;
+        (ticks < 1 << self.timestamp_bits()).then_some(ticks)
+    }
+
+    /// The start of the given tick.
+    fn time_of_tick(&self, tick: u64) -> SystemTime {
+        let nanos = self.tick.as_nanos() * u128::from(tick);
+        let secs = u64::try_from(nanos / 1_000_000_000).unwrap_or(u64::MAX);
+        #[allow(clippy::cast_possible_truncation)]
+        let subsec = (nanos % 1_000_000_000) as u32;
+        self.epoch + Duration::new(secs, subsec)
+    }
+}
+
+impl Clone for TimeSortedGenerator {
+    /// Clone the generator's options and its position, so the clone continues from the last ID
+    /// generated. The two then count up independently.
+    fn clone(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            tick: self.tick,
+            timestamp_chars: self.timestamp_chars,
+            last: AtomicU64::new(self.last.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl PartialEq for TimeSortedGenerator {
+    /// Generators are equal if they have the same options, regardless of the IDs they have
+    /// generated.
+    fn eq(&self, other: &Self) -> bool {
+        self.epoch == other.epoch
+            && self.tick == other.tick
+            && self.timestamp_chars == other.timestamp_chars
+    }
+}
+
+impl Eq for TimeSortedGenerator {}
+
+impl Default for TimeSortedGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdProvider for TimeSortedGenerator {
+    fn next_id(&mut self) -> TinyId {
+        TimeSortedGenerator::next_id(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn sorts_by_time() {
+        let gen = TimeSortedGenerator::new();
+        let mut previous = gen.try_id_at(at(1_000)).unwrap();
+        for secs in 1_001..1_100 {
+            let id = gen.try_id_at(at(secs)).unwrap();
+            assert!(id.is_valid());
+            assert!(previous < id);
+            assert_eq!(gen.timestamp(id), Some(at(secs)));
+            previous = id;
+        }
+        assert!(gen.next_id().is_valid());
+        assert_eq!(gen.timestamp(TinyId::null()), None);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn custom_epoch() {
+        let epoch = at(1_600_000_000);
+        let gen = TimeSortedGenerator::new()
+            .with_epoch(epoch)
+            .with_tick(Duration::from_secs(1))
+            .with_timestamp_chars(5);
+        assert_eq!(gen.epoch(), epoch);
+        assert_eq!(gen.tick(), Duration::from_secs(1));
+        assert_eq!(gen.timestamp_chars(), 5);
+        assert_eq!(gen.latest(), epoch + Duration::from_secs((1 << 30) - 1));
+
+        // The epoch itself is the first tick, so the timestamp letters are all the first letter.
+        let first = gen.try_id_at(epoch).unwrap();
+        assert!(first.to_string().starts_with("-----"));
+        assert_eq!(gen.timestamp(first), Some(epoch));
+        let later = gen
+            .try_id_at(epoch + Duration::from_millis(64_500))
+            .unwrap();
+        assert!(later.to_string().starts_with("---0-"));
+        assert_eq!(gen.timestamp(later), Some(epoch + Duration::from_secs(64)));
+
+        assert_eq!(
+            gen.try_id_at(epoch - Duration::from_secs(1)),
+            Err(TinyIdError::GenerationFailure)
+        );
+        assert!(gen.try_id_at(gen.latest()).is_ok());
+        assert_eq!(
+            gen.try_id_at(gen.latest() + Duration::from_secs(1)),
+            Err(TinyIdError::GenerationFailure)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn options_are_clamped() {
+        let gen = TimeSortedGenerator::new()
+            .with_tick(Duration::ZERO)
+            .with_timestamp_chars(0);
+        assert_eq!(gen.tick(), Duration::from_nanos(1));
+        assert_eq!(gen.timestamp_chars(), 1);
+        let all_time = TimeSortedGenerator::new()
+            .with_epoch(at(0))
+            .with_tick(Duration::from_secs(1))
+            .with_timestamp_chars(9);
+        assert_eq!(all_time.timestamp_chars(), 8);
+        // With no random letters the ID is entirely determined by the time.
+        assert_eq!(
+            all_time.try_id_at(at(65)),
+            Ok(TinyId::from_str_unchecked("------00"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn monotonic_within_tick() {
+        let gen = TimeSortedGenerator::new();
+        let time = at(1_000);
+        let mut previous = gen.try_next_id_at(time).unwrap();
+        for _ in 0..10 {
+            let id = gen.try_next_id_at(time).unwrap();
+            assert!(previous < id);
+            previous = id;
+        }
+        // The clock going backwards still gives increasing IDs.
+        let id = gen.try_next_id_at(at(999)).unwrap();
+        assert!(previous < id);
+        previous = id;
+        // A new tick draws new randomness.
+        let next_tick = gen.try_next_id_at(at(1_001)).unwrap();
+        assert!(previous < next_tick);
+        assert_eq!(gen.timestamp(next_tick), Some(at(1_001)));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn full_tick_carries_over() {
+        // With no random letters each tick holds a single ID.
+        let gen = TimeSortedGenerator::new()
+            .with_tick(Duration::from_secs(1))
+            .with_timestamp_chars(8);
+        let time = at(1_000);
+        let first = gen.try_next_id_at(time).unwrap();
+        let second = gen.try_next_id_at(time).unwrap();
+        assert!(first < second);
+        assert_eq!(gen.timestamp(first), Some(time));
+        assert_eq!(gen.timestamp(second), Some(at(1_001)));
+
+        let gen = TimeSortedGenerator::new();
+        let ids = (0..1_000)
+            .map(|_| gen.try_next_id_at(time).unwrap())
+            .collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(gen.timestamp(ids[999]).unwrap() > time);
+
+        let gen = TimeSortedGenerator::new().with_timestamp_chars(8);
+        assert!(gen.try_next_id_at(gen.latest()).is_ok());
+        assert_eq!(
+            gen.try_next_id_at(gen.latest()),
+            Err(TinyIdError::GenerationFailure)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn many_in_a_row() {
+        let gen = TimeSortedGenerator::new();
+        let mut previous = gen.next_id();
+        for _ in 0..100_000 {
+            let id = gen.next_id();
+            assert!(previous < id);
+            previous = id;
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn monotonic_across_threads() {
+        let gen = std::sync::Arc::new(
+            TimeSortedGenerator::new()
+                .with_epoch(SystemTime::now())
+                .with_timestamp_chars(4),
+        );
+        let handles = (0..4)
+            .map(|_| {
+                let gen = gen.clone();
+                std::thread::spawn(move || (0..1_000).map(|_| gen.next_id()).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        let mut all = std::collections::HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert!(ids.windows(2).all(|w| w[0] < w[1]));
+            all.extend(ids);
+        }
+        assert_eq!(all.len(), 4_000);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn clone_and_eq() {
+        let gen = TimeSortedGenerator::new();
+        let id = gen.next_id();
+        let clone = gen.clone();
+        assert_eq!(gen, clone);
+        assert!(clone.next_id() > id);
+        assert_ne!(gen, TimeSortedGenerator::new().with_timestamp_chars(6));
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Signed tokens, so an ID handed to a client can later be checked as server-issued without a
+//! database lookup.
+//!
+//! A token is the ID, a `.`, and an HMAC-SHA256 of the ID's 8 bytes encoded as unpadded
+//! base64url (e.g. `abcdefgh.Xo2...`). See [`TinyId::sign`] and [`TinyId::verify`].
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{TinyId, TinyIdError};
+
+/// The character separating the ID from its signature in a token.
+pub const SEPARATOR: char = '.';
+
+/// The RFC 4648 base64url alphabet.
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error returned when a token fails verification.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenError {
+    /// The token is not an ID and a signature separated by [`SEPARATOR`].
+    Malformed,
+    /// The ID part of the token is not a valid [`TinyId`].
+    InvalidId(TinyIdError),
+    /// The signature does not match the ID for the given key.
+    BadSignature,
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Malformed => write!(f, "Malformed token"),
+            TokenError::InvalidId(err) => write!(f, "Invalid ID in token: {err}"),
+            TokenError::BadSignature => write!(f, "Token signature does not match"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// Compute the encoded signature of `id` with `key`.
+fn signature(id: TinyId, key: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&id.to_bytes());
+    let tag = mac.finalize().into_bytes();
+
+    let mut out = String::with_capacity(43);
+    for chunk in tag.chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[(bits >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+/// Compare two byte strings in constant time (for equal lengths).
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl TinyId {
+    /// Create a signed token for this [`TinyId`] using `key`, which can later be checked with
+    /// [`TinyId::verify`]. Anyone can read the ID from the token, but only holders of `key` can
+    /// create a token that verifies.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::random();
+    /// let token = id.sign(b"server secret");
+    /// assert!(token.starts_with(&format!("{id}.")));
+    /// assert_eq!(TinyId::verify(&token, b"server secret"), Ok(id));
+    /// assert!(TinyId::verify(&token, b"wrong secret").is_err());
+    /// ```
+    #[must_use]
+    pub fn sign(&self, key: &[u8]) -> String {
+        format!("{self}{SEPARATOR}{}", signature(*self, key))
+    }
+
+    /// Check that `token` was created by [`TinyId::sign`] with `key`, returning its ID.
+    ///
+    /// ## Errors
+    /// - [`TokenError::Malformed`] if `token` is not an ID and a signature.
+    /// - [`TokenError::InvalidId`] if the ID part of the token is not valid.
+    /// - [`TokenError::BadSignature`] if the signature does not match.
+    pub fn verify(token: &str, key: &[u8]) -> Result<TinyId, TokenError> {
+        let (id, sig) = token.split_once(SEPARATOR).ok_or(TokenError::Malformed)?;
+        let id = id.parse::<TinyId>().map_err(TokenError::InvalidId)?;
+        if constant_time_eq(signature(id, key).as_bytes(), sig.as_bytes()) {
+            Ok(id)
+        } else {
+            Err(TokenError::BadSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn sign_and_verify() {
+        let key = b"key";
+        for _ in 0..100 {
+            let id = TinyId::random();
+            let token = id.sign(key);
+            assert_eq!(token.len(), 8 + 1 + 43);
+            assert_eq!(TinyId::verify(&token, key), Ok(id));
+            assert_eq!(
+                TinyId::verify(&token, b"other key"),
+                Err(TokenError::BadSignature)
+            );
+        }
+
+        let token = TinyId::from_str_unchecked("abcdefgh").sign(key);
+        let forged = format!("abcdefgi{}", &token[8..]);
+        assert_eq!(TinyId::verify(&forged, key), Err(TokenError::BadSignature));
+        assert_eq!(
+            TinyId::verify(&token[..token.len() - 1], key),
+            Err(TokenError::BadSignature)
+        );
+        assert_eq!(TinyId::verify("abcdefgh", key), Err(TokenError::Malformed));
+        assert_eq!(
+            TinyId::verify(&format!("abcdefg!{}", &token[8..]), key),
+            Err(TokenError::InvalidId(TinyIdError::InvalidCharacters))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn known_signature() {
+        // HMAC-SHA256("key", "abcdefgh"), checked against an independent implementation.
+        let id = TinyId::from_str_unchecked("abcdefgh");
+        assert_eq!(
+            id.sign(b"key"),
+            "abcdefgh._aicTeT6DEtExrkK6kNCrfXyQQfiykHGKH5o7QkFDoQ"
+        );
+    }
+}
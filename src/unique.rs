@@ -0,0 +1,518 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::hash::{mix64, GAMMA};
+use crate::hooks::Hooks;
+use crate::stats::Counters;
+use crate::{GenerationStats, IdProvider, TinyId, TinyIdError};
+
+/// A single RNG state, padded out to its own cache line so that threads using different shards
+/// never contend with each other.
+#[derive(Debug)]
+#[repr(align(64))]
+pub(crate) struct Shard(AtomicU64);
+
+impl Shard {
+    /// Create a randomly seeded set of shards, enough for each thread on this machine to usually
+    /// get its own.
+    pub(crate) fn new_set() -> Box<[Shard]> {
+        let shard_count = std::thread::available_parallelism()
+            .map_or(8, |n| n.get() * 2)
+            .next_power_of_two();
+        (0..shard_count)
+            .map(|_| Shard(AtomicU64::new(crate::rng::u64())))
+            .collect()
+    }
+
+    /// Draw 64 random bits from the current thread's shard of `shards`, which must have been
+    /// created by [`Shard::new_set`].
+    pub(crate) fn next_bits(shards: &[Shard]) -> u64 {
+        let shard = &shards[shard_hint() & (shards.len() - 1)].0;
+        mix64(
+            shard
+                .fetch_add(GAMMA, Ordering::Relaxed)
+                .wrapping_add(GAMMA),
+        )
+    }
+}
+
+/// A thread-safe generator which never returns the same [`TinyId`] twice.
+///
+/// The generator is `Send + Sync` and every method takes `&self`, so a single instance can be
+/// shared between any number of threads (by reference or in an [`Arc`](std::sync::Arc)) without a
+/// [`Mutex`](std::sync::Mutex):
+/// - RNG state is split into cache-line padded shards, one per thread (round-robin), each of
+///   which is advanced with a single atomic `fetch_add`.
+/// - Issued IDs are tracked in a pre-allocated open-addressing table which is claimed with
+///   atomic compare-and-swap, so checking for and recording a new ID never blocks.
+///
+/// Because the table is allocated up-front, the generator has a fixed `capacity`; once that many
+/// IDs have been issued, [`UniqueGenerator::try_next_id`] fails. The table uses 16 bytes of
+/// memory per unit of capacity, and capacity is limited to [`UniqueGenerator::MAX_CAPACITY`].
+///
+/// ## Hooks
+/// Callbacks registered with [`UniqueGenerator::on_generate`] and
+/// [`UniqueGenerator::on_collision`] are called for every issued ID and every colliding candidate,
+/// so applications can audit, log, or persist IDs without wrapping the generator. They run on the
+/// generating thread, before the ID is returned, so they should be quick.
+///
+/// ## Example
+/// ```
+/// use tinyid::UniqueGenerator;
+///
+/// let gen = UniqueGenerator::with_capacity(1_000);
+/// let ids = std::thread::scope(|s| {
+///     let a = s.spawn(|| (0..500).map(|_| gen.next_id()).collect::<Vec<_>>());
+///     let b = s.spawn(|| (0..500).map(|_| gen.next_id()).collect::<Vec<_>>());
+///     let mut ids = a.join().unwrap();
+///     ids.extend(b.join().unwrap());
+///     ids
+/// });
+/// assert_eq!(gen.len(), 1_000);
+/// assert!(ids.iter().all(|id| gen.contains(*id)));
+/// assert!(gen.try_next_id().is_err());
+/// ```
+#[derive(Debug)]
+pub struct UniqueGenerator {
+    shards: Box<[Shard]>,
+    table: Box<[AtomicU64]>,
+    capacity: usize,
+    len: AtomicUsize,
+    counters: Counters,
+    hooks: Hooks,
+}
+
+impl UniqueGenerator {
+    /// The largest capacity a [`UniqueGenerator`] can have, whose table takes 16 GiB.
+    pub const MAX_CAPACITY: usize = 1 << 30;
+
+    /// Create a new [`UniqueGenerator`] which can issue up to `capacity` unique IDs. `capacity`
+    /// is clamped to [`UniqueGenerator::MAX_CAPACITY`].
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.min(Self::MAX_CAPACITY);
+        let shards = Shard::new_set();
+        let table = (0..(capacity.max(1) * 2).next_power_of_two())
+            .map(|_| AtomicU64::new(0))
+            .collect();
+        Self {
+            shards,
+            table,
+            capacity,
+            len: AtomicUsize::new(0),
+            counters: Counters::default(),
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Call `hook` with every ID this generator issues, after any hooks registered before it.
+    /// Hooks are not saved with the generator.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use tinyid::UniqueGenerator;
+    ///
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let gen = UniqueGenerator::with_capacity(10).on_generate({
+    ///     let log = Arc::clone(&log);
+    ///     move |id| log.lock().unwrap().push(id)
+    /// });
+    /// let id = gen.next_id();
+    /// assert_eq!(*log.lock().unwrap(), [id]);
+    /// ```
+    #[must_use]
+    pub fn on_generate(mut self, hook: impl Fn(TinyId) + Send + Sync + 'static) -> Self {
+        self.hooks.on_generate(hook);
+        self
+    }
+
+    /// Call `hook` with every random candidate that is rejected because this generator already
+    /// issued it, after any hooks registered before it. A rising collision rate means the
+    /// generator is filling up. Hooks are not saved with the generator.
+    #[must_use]
+    pub fn on_collision(mut self, hook: impl Fn(TinyId) + Send + Sync + 'static) -> Self {
+        self.hooks.on_collision(hook);
+        self
+    }
+
+    /// Generate a new [`TinyId`] that this generator has never returned before.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::GenerationFailure`] if the generator has already issued `capacity` IDs.
+    pub fn try_next_id(&self) -> Result<TinyId, TinyIdError> {
+        if self.len.fetch_add(1, Ordering::AcqRel) >= self.capacity {
+            self.len.fetch_sub(1, Ordering::AcqRel);
+            return Err(TinyIdError::GenerationFailure);
+        }
+
+        self.counters.time(|| loop {
+            let id = TinyId::from_random_bits(Shard::next_bits(&self.shards));
+            if self.insert(id) {
+                self.counters.issued();
+                self.hooks.generated(id);
+                return Ok(id);
+            }
+            self.counters.collision();
+            self.hooks.collided(id);
+            self.counters.retry();
+        })
+    }
+
+    /// Generate a new [`TinyId`] that this generator has never returned before.
+    ///
+    /// ## Panics
+    /// If the generator has already issued `capacity` IDs, see [`UniqueGenerator::try_next_id`].
+    #[must_use]
+    pub fn next_id(&self) -> TinyId {
+        self.try_next_id()
+            .expect("UniqueGenerator has already issued `capacity` IDs")
+    }
+
+    /// Checks whether this generator has issued the given ID.
+    #[must_use]
+    pub fn contains(&self, id: TinyId) -> bool {
+        let value = id.to_u64();
+        if value == 0 {
+            return false;
+        }
+        self.probe(value)
+            .map(|slot| slot.load(Ordering::Acquire))
+            .take_while(|&current| current != 0)
+            .any(|current| current == value)
+    }
+
+    /// The number of IDs this generator has issued.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire).min(self.capacity)
+    }
+
+    /// Checks whether this generator has not issued any IDs yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The maximum number of IDs this generator can issue.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get a snapshot of this generator's activity. Every collision causes exactly one retry, and
+    /// occupancy is the fraction of `capacity` that has been issued.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::UniqueGenerator;
+    ///
+    /// let gen = UniqueGenerator::with_capacity(4);
+    /// let _ = gen.next_id();
+    /// let stats = gen.stats();
+    /// assert_eq!(stats.issued, 1);
+    /// assert_eq!(stats.occupancy, 0.25);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn stats(&self) -> GenerationStats {
+        let occupancy = if self.capacity == 0 {
+            1.0
+        } else {
+            self.len() as f64 / self.capacity as f64
+        };
+        self.counters.snapshot(occupancy)
+    }
+
+    /// Iterates over the table slots `value` may be stored in, in probe order.
+    #[allow(clippy::cast_possible_truncation)]
+    fn probe(&self, value: u64) -> impl Iterator<Item = &AtomicU64> {
+        let mask = self.table.len() - 1;
+        let start = mix64(value) as usize & mask;
+        (0..self.table.len()).map(move |i| &self.table[(start + i) & mask])
+    }
+
+    /// Records `id` in the table, returning `false` if it was already there. The caller must have
+    /// already reserved room for it in `len`.
+    fn insert(&self, id: TinyId) -> bool {
+        let value = id.to_u64();
+        for slot in self.probe(value) {
+            match slot.compare_exchange(0, value, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return true,
+                Err(current) if current == value => return false,
+                Err(_) => {}
+            }
+        }
+        // The table is always at least twice the capacity, so there is always an empty slot.
+        unreachable!("UniqueGenerator table is full")
+    }
+}
+
+/// The serialized form of a [`UniqueGenerator`]: its capacity, the state of each RNG shard, and
+/// every ID it has issued.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "UniqueGenerator")]
+struct UniqueGeneratorState {
+    capacity: usize,
+    shards: Vec<u64>,
+    ids: Vec<TinyId>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for UniqueGenerator {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        UniqueGeneratorState {
+            capacity: self.capacity,
+            shards: self
+                .shards
+                .iter()
+                .map(|shard| shard.0.load(Ordering::Acquire))
+                .collect(),
+            ids: self
+                .table
+                .iter()
+                .map(|slot| slot.load(Ordering::Acquire))
+                .filter(|&value| value != 0)
+                .map(TinyId::from_u64_unchecked)
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Restores a [`UniqueGenerator`]. If this machine uses more shards than the saved generator had,
+/// the extra shards are freshly seeded.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UniqueGenerator {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let state = UniqueGeneratorState::deserialize(deserializer)?;
+        if state.capacity > Self::MAX_CAPACITY {
+            return Err(D::Error::custom(
+                "UniqueGenerator capacity is larger than MAX_CAPACITY",
+            ));
+        }
+        if state.ids.len() > state.capacity {
+            return Err(D::Error::custom(
+                "UniqueGenerator has more IDs than capacity",
+            ));
+        }
+        let gen = Self::with_capacity(state.capacity);
+        for (shard, saved) in gen.shards.iter().zip(state.shards) {
+            shard.0.store(saved, Ordering::Release);
+        }
+        for id in state.ids {
+            if !id.is_valid() {
+                return Err(D::Error::custom(
+                    "UniqueGenerator cannot contain an invalid ID",
+                ));
+            }
+            gen.len.fetch_add(1, Ordering::AcqRel);
+            if !gen.insert(id) {
+                return Err(D::Error::custom("UniqueGenerator contains a duplicate ID"));
+            }
+        }
+        Ok(gen)
+    }
+}
+
+impl IdProvider for UniqueGenerator {
+    fn next_id(&mut self) -> TinyId {
+        UniqueGenerator::next_id(self)
+    }
+}
+
+impl IdProvider for &UniqueGenerator {
+    fn next_id(&mut self) -> TinyId {
+        UniqueGenerator::next_id(self)
+    }
+}
+
+/// Get this thread's shard hint, which is assigned round-robin the first time each thread asks
+/// for one.
+fn shard_hint() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static HINT: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    HINT.with(|hint| *hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<UniqueGenerator>();
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn capacity() {
+        let gen = UniqueGenerator::with_capacity(3);
+        assert!(gen.is_empty());
+        let ids = [gen.next_id(), gen.next_id(), gen.next_id()];
+        assert_eq!(gen.len(), 3);
+        assert_eq!(gen.try_next_id(), Err(TinyIdError::GenerationFailure));
+        assert_eq!(gen.len(), 3);
+        for id in ids {
+            assert!(id.is_valid());
+            assert!(gen.contains(id));
+        }
+        assert!(!gen.contains(TinyId::null()));
+
+        let gen = UniqueGenerator::with_capacity(0);
+        assert!(gen.try_next_id().is_err());
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn duplicates_are_rejected() {
+        let gen = UniqueGenerator::with_capacity(2);
+        let id = TinyId::random();
+        gen.len.fetch_add(2, Ordering::AcqRel);
+        assert!(gen.insert(id));
+        assert!(!gen.insert(id));
+        assert!(gen.contains(id));
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn stats() {
+        let gen = UniqueGenerator::with_capacity(10);
+        assert_eq!(gen.stats(), GenerationStats::default());
+        for _ in 0..5 {
+            let _ = gen.next_id();
+        }
+        let stats = gen.stats();
+        assert_eq!(stats.issued, 5);
+        assert_eq!(stats.collisions, stats.retries);
+        assert!((stats.occupancy - 0.5).abs() < f64::EPSILON);
+        assert!(gen.try_next_id().is_ok());
+        assert!((UniqueGenerator::with_capacity(0).stats().occupancy - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn hooks() {
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let record = |kind: &'static str| {
+            let events = Arc::clone(&events);
+            move |id| events.lock().unwrap().push((kind, id))
+        };
+        let gen = UniqueGenerator::with_capacity(10)
+            .on_generate(record("first"))
+            .on_generate(record("second"))
+            .on_collision(record("collision"));
+        let a = gen.next_id();
+        assert_eq!(*events.lock().unwrap(), [("first", a), ("second", a)]);
+        events.lock().unwrap().clear();
+
+        // Rewinding every shard makes the next candidate the one that was just issued.
+        for shard in &*gen.shards {
+            shard.0.fetch_sub(GAMMA, Ordering::Relaxed);
+        }
+        let b = gen.next_id();
+        assert_ne!(a, b);
+        assert_eq!(
+            *events.lock().unwrap(),
+            [("collision", a), ("first", b), ("second", b)]
+        );
+        assert_eq!(gen.stats().collisions, 1);
+        assert!(format!("{gen:?}").contains("Hooks { generated: 2, collided: 1 }"));
+    }
+
+    #[test]
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let gen = UniqueGenerator::with_capacity(10);
+            let _ = gen.next_id();
+            for shard in &*gen.shards {
+                shard.0.fetch_sub(GAMMA, Ordering::Relaxed);
+            }
+            let _ = gen.next_id();
+        });
+        let metrics = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+            .collect::<std::collections::HashMap<_, _>>();
+        assert_eq!(
+            metrics[GenerationStats::METRIC_IDS_GENERATED],
+            DebugValue::Counter(2)
+        );
+        assert_eq!(
+            metrics[GenerationStats::METRIC_COLLISION_RETRIES],
+            DebugValue::Counter(1)
+        );
+        let DebugValue::Histogram(durations) =
+            &metrics[GenerationStats::METRIC_GENERATION_DURATION]
+        else {
+            panic!("generation duration is not a histogram");
+        };
+        assert_eq!(durations.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[cfg_attr(coverage, no_coverage)]
+    fn serde() {
+        let gen = UniqueGenerator::with_capacity(100);
+        let ids: Vec<TinyId> = (0..50).map(|_| gen.next_id()).collect();
+        let json = serde_json::to_string(&gen).unwrap();
+        let restored: UniqueGenerator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.capacity(), 100);
+        assert_eq!(restored.len(), 50);
+        assert!(ids.iter().all(|&id| restored.contains(id)));
+
+        for bad in [
+            r#"{"capacity":0,"shards":[1],"ids":[{"data":[97,97,97,97,97,97,97,97]}]}"#,
+            r#"{"capacity":18446744073709551615,"shards":[1],"ids":[]}"#,
+            r#"{"capacity":1,"shards":[1],"ids":[{"data":[0,0,0,0,0,0,0,0]}]}"#,
+            r#"{"capacity":1,"shards":[1],"ids":[{"data":[97,97,97,97,97,97,97,33]}]}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<UniqueGenerator>(bad).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn collision_test_one_million_threaded() {
+        use std::collections::HashSet;
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 125_000;
+
+        let gen = UniqueGenerator::with_capacity(THREADS * PER_THREAD);
+        let ids: Vec<Vec<TinyId>> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| s.spawn(|| (0..PER_THREAD).map(|_| gen.next_id()).collect()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let unique: HashSet<TinyId> = ids.into_iter().flatten().collect();
+        assert_eq!(unique.len(), THREADS * PER_THREAD);
+        assert_eq!(gen.len(), THREADS * PER_THREAD);
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{TinyId, TinyIdError};
+
+/// How strictly a [`TinyId`] is validated when it is created from untrusted input, see
+/// [`TinyId::from_bytes_with`] and [`TinyId::from_str_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Validation {
+    /// Only valid IDs are accepted, the null ID and invalid characters are rejected. This is the
+    /// same validation as [`TinyId::from_bytes`].
+    #[default]
+    Strict,
+    /// The null ID is accepted as well as valid IDs, invalid characters are still rejected.
+    Lenient,
+}
+
+impl Validation {
+    /// Check `id` against this validation level, returning it if it passes.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if `id` is not accepted at this level.
+    pub fn check(self, id: TinyId) -> Result<TinyId, TinyIdError> {
+        match self {
+            Validation::Lenient if id.is_null() => Ok(id),
+            _ if id.is_valid() => Ok(id),
+            _ => Err(TinyIdError::InvalidCharacters),
+        }
+    }
+}
+
+impl TinyId {
+    /// Attempt to create a new [`TinyId`] from the given bytes, validated according to
+    /// `validation`.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidCharacters`] if the bytes are not accepted by `validation`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{TinyId, Validation};
+    ///
+    /// assert!(TinyId::from_bytes_with(TinyId::NULL_DATA, Validation::Strict).is_err());
+    /// assert!(TinyId::from_bytes_with(TinyId::NULL_DATA, Validation::Lenient).is_ok());
+    /// ```
+    pub fn from_bytes_with(bytes: [u8; 8], validation: Validation) -> Result<Self, TinyIdError> {
+        validation.check(Self::from_bytes_unchecked(bytes))
+    }
+
+    /// Attempt to parse a [`TinyId`] from the given string, validated according to `validation`.
+    /// The null ID's string form is 8 `'\0'` characters.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if the input is not 8 bytes long.
+    /// - [`TinyIdError::InvalidCharacters`] if the input is not accepted by `validation`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::{TinyId, Validation};
+    ///
+    /// let null = TinyId::null().to_string();
+    /// assert!(TinyId::from_str_with(&null, Validation::Strict).is_err());
+    /// assert_eq!(TinyId::from_str_with(&null, Validation::Lenient), Ok(TinyId::null()));
+    /// ```
+    pub fn from_str_with(s: &str, validation: Validation) -> Result<Self, TinyIdError> {
+        let bytes = <[u8; 8]>::try_from(s.as_bytes()).map_err(|_| TinyIdError::InvalidLength)?;
+        Self::from_bytes_with(bytes, validation)
+    }
+
+    /// Parse an optional [`TinyId`], e.g. from a form field or query parameter which may be left
+    /// blank. Surrounding whitespace is ignored, and an empty string, `-` or `null` (ignoring
+    /// ASCII case) gives `None`; anything else must be a valid ID.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if the input is not 8 bytes long.
+    /// - [`TinyIdError::InvalidCharacters`] if the input contains invalid characters.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// assert_eq!(TinyId::parse_optional(""), Ok(None));
+    /// assert_eq!(TinyId::parse_optional(" null "), Ok(None));
+    /// assert_eq!(TinyId::parse_optional("-"), Ok(None));
+    /// assert_eq!(
+    ///     TinyId::parse_optional("abcd1234"),
+    ///     Ok(Some(TinyId::from_str_unchecked("abcd1234")))
+    /// );
+    /// assert!(TinyId::parse_optional("abc").is_err());
+    /// ```
+    pub fn parse_optional(s: &str) -> Result<Option<Self>, TinyIdError> {
+        match s.trim() {
+            "" | "-" => Ok(None),
+            s if s.eq_ignore_ascii_case("null") => Ok(None),
+            s => s.parse().map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn validation() {
+        let id = TinyId::random();
+        let null = TinyId::null();
+        let bad = TinyId::from_str_unchecked("abcdefg!");
+        let partly_null = TinyId::from_bytes_unchecked(*b"abcdefg\0");
+        for level in [Validation::Strict, Validation::Lenient] {
+            assert_eq!(level.check(id), Ok(id));
+            assert_eq!(level.check(bad), Err(TinyIdError::InvalidCharacters));
+            assert_eq!(
+                level.check(partly_null),
+                Err(TinyIdError::InvalidCharacters)
+            );
+            assert_eq!(TinyId::from_str_with(&id.to_string(), level), Ok(id));
+            assert_eq!(
+                TinyId::from_str_with("abc", level),
+                Err(TinyIdError::InvalidLength)
+            );
+        }
+        assert_eq!(
+            Validation::Strict.check(null),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(Validation::Lenient.check(null), Ok(null));
+        assert_eq!(Validation::default(), Validation::Strict);
+        assert_eq!(
+            TinyId::from_bytes_with(id.to_bytes(), Validation::Strict),
+            TinyId::from_bytes(id.to_bytes())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn parse_optional() {
+        for blank in ["", "   ", "-", " - ", "null", "NULL", "Null\n"] {
+            assert_eq!(TinyId::parse_optional(blank), Ok(None), "{blank:?}");
+        }
+        let id = TinyId::random();
+        assert_eq!(TinyId::parse_optional(&id.to_string()), Ok(Some(id)));
+        assert_eq!(TinyId::parse_optional(&format!(" {id}\t")), Ok(Some(id)));
+        assert_eq!(
+            TinyId::parse_optional("--------"),
+            Ok(Some(TinyId::from_str_unchecked("--------")))
+        );
+        assert_eq!(
+            TinyId::parse_optional("nullnull"),
+            Ok(Some(TinyId::from_str_unchecked("nullnull")))
+        );
+        assert_eq!(
+            TinyId::parse_optional("none"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::parse_optional("abcdefg!"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}
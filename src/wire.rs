@@ -0,0 +1,127 @@
+// Copyright (c) 2023 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! The versioned binary wire format for [`TinyId`]s.
+//!
+//! Version `1` is 9 bytes: the version byte `0x01` followed by the 8 ASCII bytes of the ID
+//! exactly as displayed (or 8 zero bytes for the null ID). This layout is a compatibility
+//! contract: it will not change for version `1`, and any future layout will use a new version
+//! byte, with [`TinyId::from_wire`] continuing to accept every version it has ever written.
+
+use crate::{TinyId, TinyIdError};
+
+impl TinyId {
+    /// The version byte written by [`TinyId::to_wire`].
+    pub const WIRE_VERSION: u8 = 1;
+
+    /// The length of the output of [`TinyId::to_wire`], one version byte plus 8 data bytes.
+    pub const WIRE_LEN: usize = 9;
+
+    /// Encode this [`TinyId`] in the stable wire format (see the module docs): the version byte
+    /// [`TinyId::WIRE_VERSION`] followed by the 8 bytes of the ID.
+    ///
+    /// ## Example
+    /// ```
+    /// use tinyid::TinyId;
+    ///
+    /// let id = TinyId::from_str_unchecked("abcdefgh");
+    /// assert_eq!(id.to_wire(), *b"\x01abcdefgh");
+    /// assert_eq!(TinyId::from_wire(&id.to_wire()), Ok(id));
+    /// ```
+    #[must_use]
+    pub fn to_wire(self) -> [u8; Self::WIRE_LEN] {
+        let mut wire = [0u8; Self::WIRE_LEN];
+        wire[0] = Self::WIRE_VERSION;
+        wire[1..].copy_from_slice(&self.data);
+        wire
+    }
+
+    /// Decode a [`TinyId`] written by [`TinyId::to_wire`]. The null ID round-trips.
+    ///
+    /// ## Errors
+    /// - [`TinyIdError::InvalidLength`] if `bytes` is not [`TinyId::WIRE_LEN`] bytes long.
+    /// - [`TinyIdError::Conversion`] if the version byte is not a known version.
+    /// - [`TinyIdError::InvalidCharacters`] if the data bytes are not a valid or null ID.
+    pub fn from_wire(bytes: &[u8]) -> Result<Self, TinyIdError> {
+        let (&version, data) = bytes.split_first().ok_or(TinyIdError::InvalidLength)?;
+        if data.len() != 8 {
+            return Err(TinyIdError::InvalidLength);
+        }
+        if version != Self::WIRE_VERSION {
+            return Err(TinyIdError::Conversion(format!(
+                "unsupported wire format version {version}"
+            )));
+        }
+        let id = Self::from_bytes_unchecked(data.try_into()?);
+        if id.is_valid() || id.is_null() {
+            Ok(id)
+        } else {
+            Err(TinyIdError::InvalidCharacters)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn wire_roundtrip() {
+        for _ in 0..1000 {
+            let id = TinyId::random();
+            let wire = id.to_wire();
+            assert_eq!(wire[0], TinyId::WIRE_VERSION);
+            assert_eq!(wire[1..], id.to_bytes());
+            assert_eq!(TinyId::from_wire(&wire), Ok(id));
+        }
+        assert_eq!(TinyId::null().to_wire(), [1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            TinyId::from_wire(&TinyId::null().to_wire()),
+            Ok(TinyId::null())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn wire_format_is_stable() {
+        // These bytes are a compatibility contract, do not change them.
+        assert_eq!(
+            TinyId::from_wire(b"\x01abc-_019"),
+            Ok(TinyId::from_str_unchecked("abc-_019"))
+        );
+        assert_eq!(
+            TinyId::from_str_unchecked("zzzzzzzz").to_wire(),
+            *b"\x01zzzzzzzz"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(coverage, no_coverage)]
+    fn wire_errors() {
+        assert_eq!(TinyId::from_wire(b""), Err(TinyIdError::InvalidLength));
+        assert_eq!(
+            TinyId::from_wire(b"\x01abc"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert_eq!(
+            TinyId::from_wire(b"\x01abcdefghi"),
+            Err(TinyIdError::InvalidLength)
+        );
+        assert!(matches!(
+            TinyId::from_wire(b"\x02abcdefgh"),
+            Err(TinyIdError::Conversion(_))
+        ));
+        assert_eq!(
+            TinyId::from_wire(b"\x01abc!efgh"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+        assert_eq!(
+            TinyId::from_wire(b"\x01abc\0efgh"),
+            Err(TinyIdError::InvalidCharacters)
+        );
+    }
+}